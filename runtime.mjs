@@ -0,0 +1,19 @@
+// Runtime helpers that code generated with the `runtimeHelpers` option calls, instead of inlining the same boilerplate
+// for every match of a glob import.
+
+/**
+ * Get a `.then()` handler that picks the export `name` from a dynamically imported module.
+ */
+export function pick(name) {
+  return (m) => m[name];
+}
+
+/**
+ * Get a `.catch()` handler that re-throws the error of a failed dynamic import of `importedPath`, wrapped in an error
+ * that names the match and keeps the original error as its `cause`.
+ */
+export function wrapImportError(importedPath) {
+  return (cause) => {
+    throw Object.assign(new Error(`Failed to import ${importedPath}`, { cause }), { importedPath });
+  };
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+use swc_core::common::comments::Comments;
+use swc_core::common::{Span, Spanned};
+use swc_core::ecma::ast::{CallExpr, Module, ModuleItem};
+use swc_core::ecma::visit::{Visit, VisitWith};
+
+use crate::config::Config;
+use crate::transformer::get_matches;
+use crate::utils::update_json_file;
+use crate::ImportGlobArrayPlugin;
+
+/// A glob import, import-equals declaration, re-export, or resolve call that was found in a program, along with the
+/// files that its pattern matches.
+#[derive(Clone, Debug)]
+pub struct GlobImport {
+    /// The matches of the pattern, in the order they would be imported in.
+    pub matches: Vec<GlobMatch>,
+    /// The glob pattern, after any `patternRewrites` were applied and without the source's query.
+    pub pattern: String,
+    /// The span of the import (or call) in the program.
+    pub span: Span,
+}
+
+/// A file that the pattern of a [GlobImport](GlobImport) matches.
+#[derive(Clone, Debug)]
+pub struct GlobMatch {
+    /// The absolute path of the file.
+    pub absolute_path: String,
+    /// The specifier that the file would be imported with.
+    pub imported_path: String,
+}
+
+/// Collects the glob imports of a module, in the order they appear in, without changing the module.
+struct GlobImportCollector<'a, C: Comments> {
+    imports: Vec<GlobImport>,
+    plugin: &'a ImportGlobArrayPlugin<C>,
+}
+
+impl<C: Comments> GlobImportCollector<'_, C> {
+    fn push(&mut self, span: Span, pattern: String, config: &Config) {
+        let matches = get_matches(self.plugin, config, &pattern)
+            .map(|(matches, _)| matches)
+            .unwrap_or_default();
+
+        self.imports.push(GlobImport {
+            matches: matches
+                .into_iter()
+                .map(|import_paths| GlobMatch {
+                    absolute_path: import_paths.absolute_path,
+                    imported_path: import_paths.imported_path,
+                })
+                .collect(),
+            pattern,
+            span,
+        });
+    }
+}
+
+impl<C: Comments> Visit for GlobImportCollector<'_, C> {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        call.visit_children_with(self);
+
+        if let Some(pattern) = self.plugin.get_resolve_call_pattern(call) {
+            let config = self.plugin.get_import_config(call.span);
+            self.push(call.span, pattern, &config);
+        }
+    }
+
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        match self.plugin.get_item_glob_pattern(item) {
+            Some(pattern) => {
+                let config = self.plugin.get_item_config(item);
+                self.push(item.span(), pattern, &config);
+            }
+            None => item.visit_children_with(self),
+        }
+    }
+}
+
+/// Collect every glob import of `module`, along with its matches, without expanding any of them.
+pub(crate) fn collect_glob_imports<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    module: &Module,
+) -> Vec<GlobImport> {
+    let mut collector = GlobImportCollector {
+        imports: vec![],
+        plugin,
+    };
+    module.visit_with(&mut collector);
+    collector.imports
+}
+
+/// Replace the entry of `importer` in the analysis file at `analysis_path` with its glob imports, i.e., the pattern,
+/// location, and matches of each, where `get_location` locates a span in the importer. Importers without any glob
+/// imports are removed.
+pub(crate) fn write_analysis_entry(
+    analysis_path: &Path,
+    importer: &str,
+    imports: &[GlobImport],
+    get_location: impl Fn(Span) -> Value,
+) -> Result<(), String> {
+    let entry: Vec<Value> = imports
+        .iter()
+        .map(|import| {
+            let matches: Vec<Value> = import
+                .matches
+                .iter()
+                .map(|glob_match| {
+                    json!({
+                        "absolutePath": glob_match.absolute_path,
+                        "importedPath": glob_match.imported_path,
+                    })
+                })
+                .collect();
+            json!({
+                "matches": matches,
+                "pattern": import.pattern,
+                "span": get_location(import.span),
+            })
+        })
+        .collect();
+
+    update_json_file(analysis_path, |entries| {
+        if entry.is_empty() {
+            entries.remove(importer);
+        } else {
+            entries.insert(importer.to_owned(), Value::from(entry));
+        }
+    })
+}
@@ -0,0 +1,12 @@
+use std::fs;
+
+/// Write the JSON Schema of the plugin's options to `config.schema.json` in the current directory.
+fn main() {
+    fs::write(
+        "config.schema.json",
+        swc_import_glob_array_plugin::config_schema(),
+    )
+    .unwrap_or_else(|err| {
+        panic!("Import Glob Array Plugin couldn't write config.schema.json: {err}")
+    });
+}
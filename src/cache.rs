@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde_json::{Map, Value};
+
+use crate::manifest::get_globbed_dirs;
+use crate::utils::update_json_file;
+
+/// Cache of what globs matched, shared by every process that transforms files of the project through a JSON file, so
+/// that workers which expand the same globs don't each walk the same directories. Each entry is keyed by the glob's
+/// path and holds the modification time of every directory the glob looked into, i.e., its fingerprint, along with
+/// the paths it matched. Adding or removing a file changes the modification time of its directory, so an entry is only
+/// used while its fingerprint still holds.
+///
+/// Entries are read once per file, and the entries of globs that were walked are written back once the file is
+/// transformed. Processes that update the cache at the same time may drop each other's entries, which only means that
+/// those globs are walked again.
+#[derive(Debug, Default)]
+pub(crate) struct GlobCache {
+    entries: Option<Map<String, Value>>,
+    updates: Map<String, Value>,
+}
+
+impl GlobCache {
+    /// Get the paths that the glob at `glob_path` matched, unless it isn't cached yet or a directory it looked into has
+    /// changed since.
+    pub(crate) fn get(&mut self, cache_path: &Path, glob_path: &str) -> Option<Vec<PathBuf>> {
+        let entries = self.entries.get_or_insert_with(|| {
+            fs::read_to_string(cache_path)
+                .ok()
+                .and_then(|contents| serde_json::from_str(&contents).ok())
+                .unwrap_or_default()
+        });
+        let entry = entries.get(glob_path)?;
+
+        let is_fresh = entry.get("dirs")?.as_object()?.iter().all(|(dir, mtime)| {
+            get_dir_mtime(Path::new(dir)).map_or(false, |actual| Some(actual) == mtime.as_u64())
+        });
+        if !is_fresh {
+            return None;
+        }
+
+        entry
+            .get("paths")?
+            .as_array()?
+            .iter()
+            .map(|path| path.as_str().map(PathBuf::from))
+            .collect()
+    }
+
+    /// Cache the `paths` that the glob at `glob_path`, whose base directory is `base`, matched. Globs that don't look
+    /// into any directory (e.g., because their base directory doesn't exist yet), or that look into a directory whose
+    /// modification time can't be read, aren't cached.
+    pub(crate) fn insert(&mut self, glob_path: &str, base: &Path, paths: &[PathBuf]) {
+        let dirs: Option<Map<String, Value>> = get_globbed_dirs(Path::new(glob_path), base)
+            .iter()
+            .map(|dir| Some((dir.to_str()?.to_owned(), Value::from(get_dir_mtime(dir)?))))
+            .collect();
+        let Some(dirs) = dirs.filter(|dirs| !dirs.is_empty()) else {
+            return;
+        };
+        let paths: Vec<Value> = paths
+            .iter()
+            .filter_map(|path| path.to_str().map(Value::from))
+            .collect();
+
+        let mut entry = Map::new();
+        entry.insert("dirs".to_owned(), Value::Object(dirs));
+        entry.insert("paths".to_owned(), Value::from(paths));
+        self.updates
+            .insert(glob_path.to_owned(), Value::Object(entry));
+    }
+
+    /// Write the entries of the globs that were walked since the cache was last written to the file at `cache_path`. A
+    /// cache file that can't be read (e.g., because a process was stopped while writing it) is started over, and the
+    /// file is replaced in one step, so other processes never read it half written. See
+    /// [update_json_file](update_json_file).
+    pub(crate) fn write(&mut self, cache_path: &Path) -> Result<(), String> {
+        let updates = std::mem::take(&mut self.updates);
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        update_json_file(cache_path, |entries| entries.extend(updates))
+    }
+}
+
+/// Get the modification time of a directory, in nanoseconds since the Unix epoch.
+fn get_dir_mtime(dir: &Path) -> Option<u64> {
+    let modified = fs::metadata(dir).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    u64::try_from(nanos).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{get_dir_mtime, GlobCache};
+    use crate::test_utils::temp_dir;
+
+    #[test]
+    fn glob_is_expanded_again_once_its_dir_changes() {
+        let root = temp_dir("glob-cache");
+        let docs = root.join("docs");
+        let cache_path = root.join("cache.json");
+        let glob_path = docs.join("*.md");
+        let glob_path = glob_path.to_str().unwrap();
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("a.md"), "").unwrap();
+
+        let mut cache = GlobCache::default();
+        assert_eq!(cache.get(&cache_path, glob_path), None);
+        cache.insert(glob_path, &docs, &[docs.join("a.md")]);
+        cache.write(&cache_path).unwrap();
+
+        let mut cache = GlobCache::default();
+        assert_eq!(
+            cache.get(&cache_path, glob_path),
+            Some(vec![docs.join("a.md")])
+        );
+
+        // Add a file until the directory's modification time changes, in case the file system's is coarse.
+        let mtime = get_dir_mtime(&docs);
+        while get_dir_mtime(&docs) == mtime {
+            thread::sleep(Duration::from_millis(10));
+            let _ = fs::remove_file(docs.join("b.md"));
+            fs::write(docs.join("b.md"), "").unwrap();
+        }
+
+        let mut cache = GlobCache::default();
+        assert_eq!(cache.get(&cache_path, glob_path), None);
+    }
+
+    #[test]
+    fn torn_cache_file_is_started_over() {
+        let root = temp_dir("torn-glob-cache");
+        let docs = root.join("docs");
+        let cache_path = root.join("cache.json");
+        let glob_path = docs.join("*.md");
+        let glob_path = glob_path.to_str().unwrap();
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("a.md"), "").unwrap();
+        fs::write(&cache_path, r#"{ "/other/*.md": { "dirs": { "/oth"#).unwrap();
+
+        let mut cache = GlobCache::default();
+        assert_eq!(cache.get(&cache_path, glob_path), None);
+        cache.insert(glob_path, &docs, &[docs.join("a.md")]);
+        cache.write(&cache_path).unwrap();
+
+        let mut cache = GlobCache::default();
+        assert_eq!(
+            cache.get(&cache_path, glob_path),
+            Some(vec![docs.join("a.md")])
+        );
+    }
+}
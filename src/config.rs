@@ -0,0 +1,826 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use swc_core::ecma::ast::VarDeclKind;
+
+use crate::filter::PathFilter;
+use crate::utils::{get_edit_distance, is_binding_ident, parse_ts_type};
+
+/// Options that can be passed to the plugin through `jsc.experimental.plugins` in `.swcrc`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct Config {
+    /// Await the imports of the [LazyEager](Mode::LazyEager) mode with `Promise.allSettled()`, so a match that fails
+    /// to load doesn't reject the whole import. Each element becomes a settled result with the match's `_importMeta`
+    /// object, e.g., `{ status: "fulfilled", value, meta }`.
+    pub(crate) all_settled: bool,
+    /// Path, relative to the project root, of the JSON file that the glob imports of each file are reported in when
+    /// `analyze_only` is set.
+    pub(crate) analysis_file: String,
+    /// Leave every file untouched, and only report its glob imports, with their locations and matches, in the JSON
+    /// file at `analysis_file`. See [collect_glob_imports](crate::analyze::collect_glob_imports).
+    pub(crate) analyze_only: bool,
+    /// Path, relative to the project root, of a JSON file that caches what each glob matched, so that the processes of
+    /// a build share their directory walks. See [GlobCache](crate::cache::GlobCache).
+    pub(crate) cache: Option<String>,
+    /// Template for a `webpackChunkName` magic comment that is injected into every dynamic `import()` that gets
+    /// emitted. See [render_template](crate::template::render_template) for the supported placeholders.
+    pub(crate) chunk_name: Option<String>,
+    /// Write specifiers that point at an `index` file as their directory, e.g., `./widget` instead of
+    /// `./widget/index.ts`. Ignored for the [Deno](Target::Deno) target, which requires explicit extensions.
+    pub(crate) collapse_index: bool,
+    /// Reproduce the output of another implementation of the plugin, to ease migrating from it.
+    pub(crate) compat: Compat,
+    /// Conditions that are matched, in addition to `default`, when resolving package globs through the `exports` field
+    /// of a package.
+    pub(crate) conditions: Vec<String>,
+    /// Emit a marker alongside each expanded import that lists the directories that were globbed over, so bundler
+    /// integrations can register them as context dependencies and rebuild when files are added or removed.
+    pub(crate) context_dependencies: ContextDependencies,
+    /// Name of the no-op helper that gets called when `context_dependencies` is set to
+    /// [Call](ContextDependencies::Call).
+    pub(crate) context_dependency_callee: String,
+    /// Regular expression that the contents of each match must match to be kept, or must not match when it starts with
+    /// `!`, e.g., `export const route`. See [PathFilter::keep_contents](PathFilter::keep_contents).
+    pub(crate) content_filter: Option<String>,
+    /// Parse `.yaml`, `.yml`, and `.toml` matches while transforming, and inline them as object literals, like JSON
+    /// files are in the [Raw](Mode::Raw) mode, so no bundler loader is needed for them.
+    pub(crate) convert_data: bool,
+    /// Replace every binding of an import with the number of its matches, e.g., `const count = 12;`, without importing
+    /// any of them.
+    pub(crate) count_only: bool,
+    /// The kind of variable declaration that generated arrays are assigned with.
+    pub(crate) declaration_kind: DeclarationKind,
+    /// Skip TypeScript declaration files (e.g., `.d.ts`), and anything in `node_modules` or version control
+    /// directories, below the glob's base directory. See [is_junk_path](crate::filter::is_junk_path).
+    pub(crate) default_excludes: bool,
+    /// Fields of `_importMeta` objects that are only emitted in development, e.g., `["absolutePath"]`, and are left out
+    /// when SWC's environment is `production`.
+    pub(crate) dev_only_meta: Vec<String>,
+    /// Leave out the bindings of glob imports that the module never references, so an unused import doesn't load a
+    /// whole directory. When only the `_importMeta` binding is used, the matches aren't imported at all.
+    pub(crate) drop_unused: bool,
+    /// Keep an index of every glob import in the project, with its location and matches, in `.import-glob-index.json`
+    /// at the project root, for editor extensions to navigate from a glob import to the files it matched.
+    pub(crate) editor_index: bool,
+    /// Name of a helper that each element is passed to, along with the match's `_importMeta` object, e.g.,
+    /// `defineRoute(_iga1, { ... })`, so frameworks can enforce the shape of elements where they're defined.
+    pub(crate) element_wrapper: Option<String>,
+    /// Module that `element_wrapper` is imported from. When it isn't set, the helper is expected to be in scope.
+    pub(crate) element_wrapper_source: Option<String>,
+    /// Record how long each file took to transform, how many globs it expanded and files they matched, and how many
+    /// nodes it grew by, in the JSON file at `stats_file`.
+    pub(crate) emit_stats: bool,
+    /// Glob patterns, relative to the project root, of files that the plugin skips, even when they're included.
+    pub(crate) exclude: Vec<String>,
+    /// Skip tests and mocks, i.e., `*.test.*` and `*.spec.*` files and anything in `__tests__` or `__mocks__`
+    /// directories, below the glob's base directory. See [is_test_path](crate::filter::is_test_path).
+    pub(crate) exclude_tests: bool,
+    /// Options that the plugin's config, `.importglobrc.json` files, or directives that this config was merged from set
+    /// explicitly, which a [Preset](Preset) selected by a later one doesn't replace. See [merge](Config::merge).
+    #[serde(skip)]
+    pub(crate) explicit_options: Map<String, Value>,
+    /// Additionally export the binding of each match under a PascalCase name that is derived from its file name, in the
+    /// [Eager](Mode::Eager) mode.
+    pub(crate) export_bindings: bool,
+    /// Extensions to probe, in order of priority, when a pattern omits the extension, so that only one match is kept
+    /// for each logical module (e.g., `foo.ts` over `foo.js`).
+    pub(crate) extensions: Vec<String>,
+    /// Regular expression that each match's path (relative to the glob's base directory) must match to be kept, or
+    /// must not match when it starts with `!`. See [PathFilter](PathFilter).
+    pub(crate) filter: Option<String>,
+    /// Spread the matches of each import into a binding that several imports are merged into (see `merge_into`). When
+    /// unset, the binding is an array of each import's array instead, e.g., `[core, plugins]`.
+    pub(crate) flatten: bool,
+    /// Add the `lastModified` time and `lastCommit` of each match to its `_importMeta` object, from the git metadata
+    /// that was generated ahead of the build into an `.import-glob-git.json` file at the root of the project.
+    pub(crate) git_meta: bool,
+    /// Number of leading directories of each match's path (relative to the glob's base directory) that nest it in
+    /// objects keyed by those directories, rather than collecting every match into a flat array. See
+    /// [get_grouped_expr](crate::group::get_grouped_expr).
+    pub(crate) group_depth: usize,
+    /// Add the `width` and `height` of image matches to their `_importMeta` objects. See
+    /// [get_image_dimensions](crate::image::get_image_dimensions) for the supported formats.
+    pub(crate) image_dimensions: bool,
+    /// Whether matches are imported with ESM `import` declarations or CommonJS `require()` calls, so the generated
+    /// code matches the module system of the output.
+    pub(crate) import_style: ImportStyle,
+    /// Prefix that is prepended to project-relative specifiers when `specifier_style` is set to
+    /// [ImportMap](SpecifierStyle::ImportMap).
+    pub(crate) import_map_prefix: String,
+    /// Glob patterns, relative to the project root, of the only files that the plugin transforms. Every file is
+    /// transformed when it's empty.
+    pub(crate) include: Vec<String>,
+    /// Specifiers that are appended after the matches of every import, and are imported the same way, e.g., a
+    /// built-in default handler alongside globbed handlers. Relative specifiers resolve against the importing file.
+    pub(crate) inject_entries: Vec<String>,
+    /// What happens to matches that are larger than `max_inline_size` in the [Raw](Mode::Raw) mode.
+    pub(crate) inline_overflow: InlineOverflow,
+    /// Add an `isSymlink` flag to the `_importMeta` object of each match, which is set when the match is reached through
+    /// a symlink below the glob's base directory, e.g., to tell linked workspace files from vendored copies.
+    pub(crate) is_symlink: bool,
+    /// How matches whose key is already taken are handled when `keyed` is set.
+    pub(crate) key_collision: KeyCollision,
+    /// Prepended to the key of each match when `keyed` is set, e.g., `cmd:` to namespace the keys of an object that is
+    /// merged into another registry.
+    pub(crate) key_prefix: String,
+    /// Appended to the key of each match when `keyed` is set.
+    pub(crate) key_suffix: String,
+    /// Template for the key of each match when `keyed` is set. See
+    /// [render_template](crate::template::render_template) for the supported placeholders and filters.
+    pub(crate) key_template: String,
+    /// Collect matches into an object keyed by `key_template` (by default, their file name without the extension)
+    /// instead of an array.
+    pub(crate) keyed: bool,
+    /// The kind of collection that keyed matches are collected into.
+    pub(crate) keyed_output: KeyedOutput,
+    /// TypeScript type that bindings of lazy, keyed imports are annotated with in TypeScript files. Defaults to
+    /// `Record<string, () => Promise<unknown>>` (or a `Map` of the same, for the [Map](KeyedOutput::Map) output),
+    /// nested in another `Record` for each level of grouping.
+    pub(crate) lazy_type: Option<String>,
+    /// The most matches that an import keeps, after sorting and skipping `offset` matches, e.g., the latest 10 posts.
+    /// Every match is kept when it isn't set.
+    pub(crate) limit: Option<usize>,
+    /// Declare a `resolve<Binding>(path)` function alongside each binding of an import, which gives back the element of
+    /// the match whose imported path is `path`, e.g., `resolvePages("./pages/home.js")`.
+    pub(crate) lookup_helper: bool,
+    /// Path, relative to the project root, of a JSON manifest that lists every pattern that was expanded by each file,
+    /// along with its matches. The manifest is importable as `import-glob-array/manifest`.
+    pub(crate) manifest: Option<String>,
+    /// Summarize how many globs each file expanded, and how many files they matched, so glob growth can be tracked
+    /// over time.
+    pub(crate) match_report: MatchReport,
+    /// The largest file, in bytes, whose contents are inlined in the [Raw](Mode::Raw) mode. Files of any size are
+    /// inlined when it isn't set.
+    pub(crate) max_inline_size: Option<u64>,
+    /// Name of a binding that collects the matches of every import with the same `merge_into`, in the order of the
+    /// imports, e.g., `const commands = [...core, ...plugins];`. Usually set for a single import with a directive.
+    pub(crate) merge_into: Option<String>,
+    /// Merge the matches of every target of a `paths` alias, rather than only using the first target with matches.
+    pub(crate) merge_path_targets: bool,
+    /// Extra properties that are merged into every `_importMeta` object.
+    pub(crate) meta: Map<String, Value>,
+    /// Fields that `_importMeta` objects are restricted to, e.g., `["importedPath"]`, rather than every field.
+    pub(crate) meta_fields: Option<Vec<String>>,
+    /// How each matched file is turned into an element of the generated array.
+    pub(crate) mode: Mode,
+    /// The module system that sources are compiled to, which determines the extension of the artifact that each
+    /// generated specifier points at.
+    pub(crate) module_system: ModuleSystem,
+    /// How an `export * as ns from "./x/*.js"` re-export exposes its matches.
+    pub(crate) namespace_reexports: NamespaceReexports,
+    /// How many of an import's sorted matches are skipped before `limit` is applied.
+    pub(crate) offset: usize,
+    /// Aliases of patterns, in the format of `compilerOptions.paths` in `tsconfig.json`, whose targets are relative to
+    /// the project root, e.g., `"@/*": ["src/*", "generated/*"]`.
+    pub(crate) paths: BTreeMap<String, Vec<String>>,
+    /// Name of a TypeScript type alias that is declared alongside the bindings of an import in TypeScript files, as the
+    /// union of every imported path, e.g., `type MatchedPath = "./a.ts" | "./b.ts";`.
+    pub(crate) path_type: Option<String>,
+    /// Help applications warm up likely-needed chunks of lazily imported matches.
+    pub(crate) prefetch: Prefetch,
+    /// Prefix of the URLs that are emitted in the [Url](Mode::Url) mode, e.g., `/static/`, for where the deploy
+    /// pipeline serves matches from.
+    pub(crate) public_path: Option<String>,
+    /// A bundle of options for a common use case, which explicitly configured options take precedence over.
+    pub(crate) preset: Option<Preset>,
+    /// Regular expression rewrites that are applied, in order, to the source of every import before it's checked and
+    /// expanded as a glob pattern, for resolution conventions that `paths` can't express.
+    pub(crate) pattern_rewrites: Vec<PatternRewrite>,
+    /// Keep the original glob import as a line comment above the code it expands to, so the pattern can still be seen
+    /// when reading compiled output or bundle diffs.
+    pub(crate) preserve_original_import_as_comment: bool,
+    /// Expand a globbed `require.resolve()` or `import.meta.resolve()` call into a call to the same resolver for each
+    /// match, rather than into the absolute path (or `file:` URL) of each match.
+    pub(crate) preserve_resolve_calls: bool,
+    /// Add the `wordCount` and estimated `readingTime` (in minutes) of Markdown and MDX matches to their
+    /// `_importMeta` objects.
+    pub(crate) reading_time: bool,
+    /// Resolve symlinks in the `absolutePath` of each match, which is also how duplicate matches are told apart, rather
+    /// than keeping the path as it was matched. See [get_real_path](crate::utils::get_real_path).
+    pub(crate) real_paths: bool,
+    /// Add the path of each match relative to the static prefix of its pattern (e.g., `guides/intro.md` for
+    /// `./content/**/*.md`) to its `_importMeta` object, as `relativeToGlob`. The field is also added when `meta_fields`
+    /// names it.
+    pub(crate) relative_to_glob: bool,
+    /// Only expand imports whose source opts in with the `glob:` scheme, so files with glob-looking names are never
+    /// transformed by accident.
+    pub(crate) require_glob_scheme: bool,
+    /// Directories, relative to the project root, that mirror each other's layout, such as `["src", "lib"]` in a repo
+    /// that is partway through moving between them. A relative pattern in a file within one of the roots is expanded
+    /// at the same place in every root, and the matches are merged.
+    pub(crate) roots: Vec<String>,
+    /// Call the runtime helpers that ship with the plugin instead of inlining the same boilerplate for every match, such
+    /// as the export picker of lazy imports and the error handler of `wrap_lazy_errors`, which shrinks the output of
+    /// large globs. The helpers are imported once per file.
+    pub(crate) runtime_helpers: bool,
+    /// The order that matches are emitted in.
+    pub(crate) sort: Sort,
+    /// Whether matches are emitted in ascending or descending [Sort](Sort) order.
+    pub(crate) sort_order: SortOrder,
+    /// How the specifier of each match is written in the generated code.
+    pub(crate) specifier_style: SpecifierStyle,
+    /// Path, relative to the project root, of a JSON file that records a fingerprint of each pattern's matches, so
+    /// that a note is reported when a build matches a different set of files than the previous one did.
+    pub(crate) stamp_file: Option<String>,
+    /// Path, relative to the project root, of the file that `emit_stats` writes to.
+    pub(crate) stats_file: String,
+    /// The runtime that the output targets.
+    pub(crate) target: Target,
+    /// Await the imports of the [LazyEager](Mode::LazyEager) mode with top-level `await`. When unset, bindings are
+    /// filled by an async IIFE instead, whose promise is exported as `<binding>Ready`, for targets and CommonJS output
+    /// that don't support top-level `await`.
+    pub(crate) top_level_await: bool,
+    /// How `.wasm` matches are loaded.
+    pub(crate) wasm: WasmImport,
+    /// Warn about each pattern that didn't match any files when it's expanded, and emit a guard, which only runs in
+    /// development builds, that warns when a binding is empty at runtime.
+    pub(crate) warn_on_empty: bool,
+    /// Path, relative to the project root, of a JSON file that lists every directory that each file's globs looked
+    /// into, so watchers and incremental builds know which files to recompile when a directory's contents change.
+    pub(crate) watch_manifest: Option<String>,
+    /// The reading speed that `reading_time` is estimated with.
+    pub(crate) words_per_minute: u32,
+    /// Wrap each thunk of the [Lazy](Mode::Lazy) mode so a failed import is re-thrown with the specifier of the match
+    /// that failed, as the `importedPath` of the error.
+    pub(crate) wrap_lazy_errors: bool,
+}
+
+/// Check each of `options` on its own, so that unknown options and invalid values are reported along with the option
+/// they belong to, and a suggestion for misspelled options.
+fn check_options(options: &Map<String, Value>) -> Result<(), String> {
+    let Ok(Value::Object(defaults)) = serde_json::to_value(Config::default()) else {
+        return Ok(());
+    };
+
+    options.iter().try_for_each(|(key, value)| {
+        if !defaults.contains_key(key) {
+            let suggestion = defaults
+                .keys()
+                .map(|known| (get_edit_distance(key, known), known))
+                .filter(|(distance, _)| *distance <= 3)
+                .min()
+                .map(|(_, known)| format!(", did you mean `{known}`?"))
+                .unwrap_or_default();
+            return Err(format!("unknown option `{key}`{suggestion}"));
+        }
+
+        let mut config = defaults.clone();
+        config.insert(key.to_owned(), value.to_owned());
+        serde_json::from_value::<Config>(Value::Object(config))
+            .map(|_| ())
+            .map_err(|err| format!("invalid value for `{key}`: {err}"))
+    })
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Compat {
+    #[default]
+    None,
+    /// Match the output of [babel-plugin-import-glob-array](https://github.com/jescalan/babel-plugin-import-glob-array).
+    Babel,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ContextDependencies {
+    /// Don't emit any markers.
+    #[default]
+    None,
+    /// Emit a `/* import-glob-array-context: <dir> */` comment for each watched directory.
+    Comment,
+    /// Emit a call to the configured helper with each watched directory as an argument.
+    Call,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DeclarationKind {
+    #[default]
+    Const,
+    Let,
+    Var,
+}
+
+impl From<DeclarationKind> for VarDeclKind {
+    fn from(kind: DeclarationKind) -> Self {
+        match kind {
+            DeclarationKind::Const => VarDeclKind::Const,
+            DeclarationKind::Let => VarDeclKind::Let,
+            DeclarationKind::Var => VarDeclKind::Var,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ImportStyle {
+    /// Emit an `import` declaration for each match.
+    #[default]
+    Esm,
+    /// Emit a `const _iga1 = require("./x/a.js")` declaration for each match.
+    Require,
+    /// Emit a `require()` declaration for each match, using a `require` function that is created with
+    /// `createRequire(import.meta.url)` so it also works from within an ES module.
+    CreateRequire,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum InlineOverflow {
+    /// Report an error for each file that is too large.
+    #[default]
+    Error,
+    /// Import each file that is too large statically instead.
+    Import,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum KeyCollision {
+    /// Report an error for each match whose key is already taken, and skip it.
+    #[default]
+    Error,
+    /// Disambiguate the key of each match whose key is already taken with a numeric suffix, e.g., `index-2`.
+    Suffix,
+    /// Let the last match with a key replace the earlier ones.
+    LastWins,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum KeyedOutput {
+    /// Emit an object literal, e.g., `{ intro: _iga1 }`.
+    #[default]
+    Object,
+    /// Emit a `Map` in the order of the matches, e.g., `new Map([["intro", _iga1]])`, which is safe for any key,
+    /// including `__proto__` and path-like keys.
+    Map,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MatchReport {
+    /// Don't report anything.
+    #[default]
+    None,
+    /// Add a `/* import-glob-array: 2 globs matched 14 files */` comment to the top of the file.
+    Comment,
+    /// Report the same summary as a note in SWC's diagnostics.
+    Diagnostic,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Mode {
+    /// Import each match statically and collect the imported bindings.
+    #[default]
+    Eager,
+    /// Emit a thunk for each match that imports it dynamically, i.e., `() => import(...)`.
+    Lazy,
+    /// Import every match dynamically and await them all at once, i.e., `await Promise.all([import(...)])`, so matches
+    /// are split into their own chunks but are loaded before the module finishes evaluating.
+    LazyEager,
+    /// Import each match statically and merge their exports into a single object, i.e., `Object.assign({}, ...)`, such as
+    /// to build a registry of commands from a directory of modules.
+    Merged,
+    /// Inline the contents of each match, as a string or, for JSON files, as a literal.
+    Raw,
+    /// Emit the URL of each match, so assets can be referenced without being imported.
+    Url,
+    /// Emit a React Router 6.4+ route object for each match that loads its module lazily, i.e.,
+    /// `{ path, lazy: () => import(...) }`.
+    ReactRouterLazy,
+    /// Import the class map of each CSS Module (e.g., `Button.module.css`) statically, and collect them into an object
+    /// keyed by `key_template`, which defaults to the `{basename}` of each match in this mode, i.e., `{ Button: _iga1 }`.
+    CssModules,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ModuleSystem {
+    /// Point specifiers at the matched sources, as-is.
+    #[default]
+    Preserve,
+    /// Sources are compiled to ES modules, so specifiers point at `.mjs` artifacts.
+    Esm,
+    /// Sources are compiled to CommonJS modules, so specifiers point at `.cjs` artifacts.
+    #[serde(rename = "commonjs")]
+    CommonJs,
+    /// Sources are compiled to `.js` artifacts, whose module system follows the `type` of their `package.json`.
+    Package,
+}
+
+impl ModuleSystem {
+    /// Get the extension of the artifact that a source with `extension` is compiled to. Sources whose extension
+    /// already determines their module system (e.g., `.mts` or `.cjs`) always keep it, and files that aren't
+    /// JavaScript or TypeScript sources (and every file, for [Preserve](ModuleSystem::Preserve)) give back `None`.
+    pub(crate) fn get_artifact_extension(self, extension: &str) -> Option<&'static str> {
+        if self == ModuleSystem::Preserve {
+            return None;
+        }
+
+        match extension {
+            "mts" | "mjs" => Some("mjs"),
+            "cts" | "cjs" => Some("cjs"),
+            "ts" | "tsx" | "js" | "jsx" => Some(match self {
+                ModuleSystem::Esm => "mjs",
+                ModuleSystem::CommonJs => "cjs",
+                _ => "js",
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum NamespaceReexports {
+    /// Export a single binding with every match, like the equivalent `import * as ns` would, i.e., an array, or an
+    /// object when `keyed` is set.
+    #[default]
+    Aggregate,
+    /// Re-export every match as its own namespace, named after the rendered `key_template` of the match, e.g.,
+    /// `export * as click from "./handlers/click.ts"`.
+    PerMatch,
+}
+
+/// A rewrite of an import's source before it's checked and expanded as a glob pattern.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct PatternRewrite {
+    /// Regular expression that is searched for in the source.
+    pub(crate) find: String,
+    /// Replacement for every occurrence of `find`, which can refer to its capture groups, e.g., `$1`.
+    pub(crate) replace: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Prefetch {
+    /// Don't emit any hints.
+    #[default]
+    None,
+    /// Export an array of the matched specifiers alongside each lazy binding, e.g., `export const docsSpecifiers`.
+    Manifest,
+    /// Inject a `webpackPrefetch: true` magic comment into every dynamic `import()`.
+    WebpackPrefetch,
+    /// Inject a `webpackPreload: true` magic comment into every dynamic `import()`.
+    WebpackPreload,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Preset {
+    /// Load matches lazily, the way Vite's `import.meta.glob` does by default.
+    Vite,
+    /// Reproduce the output of babel-plugin-import-glob-array.
+    BabelCompat,
+    /// Sort pages naturally, and describe them with reading times and image dimensions.
+    ContentSite,
+}
+
+impl Preset {
+    fn get_options(self) -> Map<String, Value> {
+        let options = match self {
+            Preset::Vite => json!({
+                "extensions": [".ts", ".tsx", ".js", ".jsx"],
+                "mode": "lazy",
+            }),
+            Preset::BabelCompat => json!({ "compat": "babel" }),
+            Preset::ContentSite => json!({
+                "imageDimensions": true,
+                "readingTime": true,
+                "sort": "natural",
+            }),
+        };
+
+        match options {
+            Value::Object(options) => options,
+            _ => Map::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Sort {
+    /// Sort by path, comparing characters one by one.
+    #[default]
+    Path,
+    /// Sort by path, comparing runs of digits by their numeric value, e.g., `page-2` before `page-10`.
+    Natural,
+    /// Sort by when each match was last modified.
+    Mtime,
+    /// Sort by a field of each match's front matter, e.g., `{ "frontmatter": "order" }`.
+    Frontmatter(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SpecifierStyle {
+    /// Relative to the importing file, e.g., `./docs/hello.md`.
+    #[default]
+    Relative,
+    /// An absolute path, e.g., `/path/to/project/docs/hello.md`, for Node.js or Electron code that loads modules
+    /// directly from disk.
+    Absolute,
+    /// An absolute `file://` URL, e.g., `file:///path/to/project/docs/hello.md`.
+    FileUrl,
+    /// Relative to the project root and prefixed with `import_map_prefix`, e.g., `/docs/hello.md`, so the specifier
+    /// can be remapped through an import map.
+    ImportMap,
+    /// Relative to the project root, without a leading `./`, e.g., `docs/hello.md`, for resolvers that resolve from the
+    /// root, and for specifiers that stay the same whichever file imports them.
+    RootRelative,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Target {
+    /// Output is consumed by a bundler or Node.js.
+    #[default]
+    Default,
+    /// Output runs directly under Deno, so every specifier keeps its explicit extension and matches are always
+    /// loaded as ES modules.
+    Deno,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WasmImport {
+    /// Import `.wasm` matches through the WebAssembly ES module integration, where a module's exports are its named
+    /// exports, so default specifiers bind the whole module instead.
+    #[default]
+    Module,
+    /// Emit the URL of each match of a `.wasm` pattern, whatever the import's mode, to be instantiated with
+    /// `WebAssembly.instantiateStreaming()`.
+    Url,
+}
+
+impl Config {
+    /// Get the TypeScript type that bindings of lazy, keyed imports are annotated with, which is either the configured
+    /// `lazy_type` or one that is derived from `keyed_output` and `group_depth`.
+    pub(crate) fn get_lazy_type(&self) -> String {
+        if let Some(lazy_type) = &self.lazy_type {
+            return lazy_type.to_owned();
+        }
+
+        let thunks = match self.keyed_output {
+            KeyedOutput::Object => "Record<string, () => Promise<unknown>>",
+            KeyedOutput::Map => "Map<string, () => Promise<unknown>>",
+        };
+        (0..self.group_depth).fold(thunks.to_owned(), |lazy_type, _| {
+            format!("Record<string, {lazy_type}>")
+        })
+    }
+
+    /// Check whether each binding of an import is an array of matches, which can be merged with the bindings of other
+    /// imports.
+    pub(crate) fn has_array_bindings(&self) -> bool {
+        !self.count_only
+            && !self.keyed
+            && self.group_depth == 0
+            && self.mode != Mode::Merged
+            && self.mode != Mode::CssModules
+    }
+
+    /// Get a copy of this config with each of `overrides` replacing the option of the same (camel-cased) name.
+    /// When the overrides select a [Preset](Preset), the preset's options are applied first, followed by the options
+    /// that were set explicitly when this config was merged, then by the overrides. That way, any option that is set
+    /// explicitly, in this layer or in one below it (e.g., the plugin's config below a `.importglobrc.json`), still
+    /// takes precedence over the preset.
+    pub(crate) fn merge(&self, overrides: Map<String, Value>) -> Result<Config, String> {
+        check_options(&overrides)?;
+        let mut value = serde_json::to_value(self).map_err(|err| err.to_string())?;
+
+        if let Value::Object(options) = &mut value {
+            if let Some(preset) = overrides.get("preset") {
+                let preset: Preset =
+                    serde_json::from_value(preset.clone()).map_err(|err| err.to_string())?;
+                options.extend(preset.get_options());
+                options.extend(self.explicit_options.clone());
+            }
+            options.extend(overrides.clone());
+        }
+
+        let mut config: Config = serde_json::from_value(value).map_err(|err| err.to_string())?;
+        config.validate()?;
+        config.explicit_options = self.explicit_options.clone();
+        config.explicit_options.extend(overrides);
+        Ok(config)
+    }
+
+    /// Parse the plugin's JSON config over the default config, then validate it.
+    pub(crate) fn parse(json: &str) -> Result<Config, String> {
+        let overrides = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        Config::default().merge(overrides)
+    }
+
+    /// Apply the `pattern_rewrites` to an import's source, in order. Rewrites whose `find` isn't a valid regular
+    /// expression are reported by [validate](Config::validate), and skipped here.
+    pub(crate) fn rewrite_pattern(&self, src: &str) -> String {
+        self.pattern_rewrites
+            .iter()
+            .fold(src.to_owned(), |src, rewrite| {
+                match Regex::new(&rewrite.find) {
+                    Ok(regex) => regex.replace_all(&src, &rewrite.replace).into_owned(),
+                    Err(_) => src,
+                }
+            })
+    }
+
+    /// Check for combinations of options that can't work together.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.target == Target::Deno && self.import_style != ImportStyle::Esm {
+            return Err("the \"deno\" target only supports the \"esm\" import style".into());
+        }
+        if self.target == Target::Deno && self.module_system != ModuleSystem::Preserve {
+            return Err(
+                "the \"deno\" target only supports the \"preserve\" module system, since it runs sources directly"
+                    .into(),
+            );
+        }
+        if self.compat == Compat::Babel
+            && (self.mode != Mode::Eager || self.import_style != ImportStyle::Esm)
+        {
+            return Err(
+                "the \"babel\" compat only supports the \"eager\" mode and the \"esm\" import style"
+                    .into(),
+            );
+        }
+        if self.compat == Compat::Babel && (self.group_depth > 0 || self.keyed) {
+            return Err("the \"babel\" compat doesn't support grouped or keyed output".into());
+        }
+        if self.mode == Mode::LazyEager && (self.group_depth > 0 || self.keyed) {
+            return Err("the \"lazyEager\" mode doesn't support grouped or keyed output".into());
+        }
+        if self.mode == Mode::Merged && (self.group_depth > 0 || self.keyed) {
+            return Err("the \"merged\" mode doesn't support grouped or keyed output".into());
+        }
+        if self.mode == Mode::LazyEager && self.element_wrapper.is_some() {
+            return Err("the \"lazyEager\" mode doesn't support an elementWrapper".into());
+        }
+        if self.runtime_helpers && self.import_style != ImportStyle::Esm {
+            return Err(
+                "runtimeHelpers are an ES module, so they only support the \"esm\" import style"
+                    .into(),
+            );
+        }
+        if self.group_depth > 0 && !self.inject_entries.is_empty() {
+            return Err(
+                "injectEntries can't be grouped, since they aren't nested in any directories"
+                    .into(),
+            );
+        }
+        if let Some(filter) = &self.filter {
+            PathFilter::new(filter).map_err(|err| format!("invalid filter: {err}"))?;
+        }
+        if let Some(content_filter) = &self.content_filter {
+            PathFilter::new(content_filter)
+                .map_err(|err| format!("invalid contentFilter: {err}"))?;
+        }
+        if let Some(lazy_type) = &self.lazy_type {
+            parse_ts_type(lazy_type).ok_or_else(|| format!("invalid lazyType: {lazy_type}"))?;
+        }
+        for rewrite in &self.pattern_rewrites {
+            Regex::new(&rewrite.find)
+                .map_err(|err| format!("invalid pattern rewrite {}: {err}", rewrite.find))?;
+        }
+        if let Some(path_type) = &self.path_type {
+            if !is_binding_ident(path_type) {
+                return Err(format!("invalid pathType: {path_type}"));
+            }
+        }
+        if let Some(merge_into) = &self.merge_into {
+            if !is_binding_ident(merge_into) {
+                return Err(format!("invalid mergeInto: {merge_into}"));
+            }
+            if !self.has_array_bindings() {
+                return Err("mergeInto only supports imports whose bindings are arrays, i.e., that aren't grouped, keyed, counted, or merged".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            all_settled: false,
+            analysis_file: "node_modules/.cache/import-glob-array/analysis.json".into(),
+            analyze_only: false,
+            cache: None,
+            chunk_name: None,
+            collapse_index: false,
+            compat: Compat::default(),
+            conditions: vec!["import".into()],
+            content_filter: None,
+            context_dependencies: ContextDependencies::default(),
+            context_dependency_callee: "__importGlobArrayContext".into(),
+            convert_data: false,
+            count_only: false,
+            declaration_kind: DeclarationKind::default(),
+            default_excludes: true,
+            dev_only_meta: vec![],
+            drop_unused: false,
+            editor_index: false,
+            element_wrapper: None,
+            element_wrapper_source: None,
+            emit_stats: false,
+            exclude: vec![],
+            exclude_tests: false,
+            explicit_options: Map::new(),
+            export_bindings: false,
+            extensions: vec![],
+            filter: None,
+            flatten: true,
+            git_meta: false,
+            group_depth: 0,
+            image_dimensions: false,
+            import_style: ImportStyle::default(),
+            include: vec![],
+            inject_entries: vec![],
+            inline_overflow: InlineOverflow::default(),
+            is_symlink: false,
+            import_map_prefix: "/".into(),
+            key_collision: KeyCollision::default(),
+            key_prefix: String::new(),
+            key_suffix: String::new(),
+            key_template: "{name}".into(),
+            keyed: false,
+            keyed_output: KeyedOutput::default(),
+            lazy_type: None,
+            limit: None,
+            lookup_helper: false,
+            manifest: None,
+            match_report: MatchReport::default(),
+            max_inline_size: None,
+            merge_into: None,
+            merge_path_targets: false,
+            meta: Map::new(),
+            meta_fields: None,
+            mode: Mode::default(),
+            module_system: ModuleSystem::default(),
+            namespace_reexports: NamespaceReexports::default(),
+            offset: 0,
+            paths: BTreeMap::new(),
+            path_type: None,
+            prefetch: Prefetch::default(),
+            preset: None,
+            pattern_rewrites: vec![],
+            preserve_original_import_as_comment: false,
+            preserve_resolve_calls: false,
+            public_path: None,
+            reading_time: false,
+            real_paths: false,
+            relative_to_glob: false,
+            require_glob_scheme: false,
+            roots: vec![],
+            runtime_helpers: false,
+            sort: Sort::default(),
+            sort_order: SortOrder::default(),
+            specifier_style: SpecifierStyle::default(),
+            stamp_file: None,
+            stats_file: "node_modules/.cache/import-glob-array/stats.json".into(),
+            target: Target::default(),
+            top_level_await: true,
+            wasm: WasmImport::default(),
+            warn_on_empty: false,
+            watch_manifest: None,
+            words_per_minute: 200,
+            wrap_lazy_errors: false,
+        }
+    }
+}
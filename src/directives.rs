@@ -0,0 +1,144 @@
+use serde_json::{Map, Value};
+use swc_core::common::comments::Comment;
+use swc_core::ecma::ast::{Expr, KeyValueProp, Lit, ObjectLit, Prop, PropName, PropOrSpread};
+
+const DIRECTIVE_PREFIX: &str = "import-glob:";
+
+const META_PREFIX: &str = "glob-meta:";
+
+/// Parameters of the query that an import's source can end with, e.g., `./posts/*.md?limit=10&offset=0`.
+const QUERY_PARAMS: [&str; 3] = ["limit", "meta", "offset"];
+
+const MODES: [&str; 8] = [
+    "cssModules",
+    "eager",
+    "lazy",
+    "lazyEager",
+    "merged",
+    "raw",
+    "reactRouterLazy",
+    "url",
+];
+
+/// Get the options that an `// import-glob: ...` directive comment overrides for a single import, e.g.,
+/// `// import-glob: lazy, sort=mtime, sortOrder=desc`.
+///
+/// Each comma-separated entry is either a `key=value` pair, where the value is parsed as JSON and falls back to a
+/// string, or a bare word. Bare words that name a [Mode](crate::config::Mode) select that mode, while any other bare
+/// word enables the option of the same name.
+pub(crate) fn get_directive_overrides(comments: &[Comment]) -> Option<Map<String, Value>> {
+    let directive = comments
+        .iter()
+        .rev()
+        .find_map(|comment| comment.text.trim().strip_prefix(DIRECTIVE_PREFIX))?;
+
+    Some(
+        directive
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('=') {
+                Some((key, value)) => (key.trim().to_owned(), parse_value(value.trim())),
+                None if MODES.contains(&entry) => ("mode".to_owned(), Value::from(entry)),
+                None => (entry.to_owned(), Value::Bool(true)),
+            })
+            .collect(),
+    )
+}
+
+fn parse_value(value: &str) -> Value {
+    serde_json::from_str(value).unwrap_or_else(|_| Value::from(value))
+}
+
+/// Get the JSON object of a `// glob-meta: {"collection": "blog"}` comment, which gets merged into every meta object
+/// of an import. Payloads that aren't a JSON object are reported as an error.
+pub(crate) fn get_meta_augmentation(
+    comments: &[Comment],
+) -> Option<Result<Map<String, Value>, String>> {
+    let payload = comments
+        .iter()
+        .find_map(|comment| comment.text.trim().strip_prefix(META_PREFIX))?;
+
+    Some(match serde_json::from_str(payload.trim()) {
+        Ok(Value::Object(meta)) => Ok(meta),
+        Ok(_) => Err("glob-meta must be a JSON object".into()),
+        Err(err) => Err(err.to_string()),
+    })
+}
+
+/// Split a query such as `?meta=importedPath,relativeToGlob&limit=10` off an import's source, giving back the source
+/// without it and the query's `key=value` parameters. Since `?` also matches any single character in a glob, only a
+/// query whose parameters are all [QUERY_PARAMS](QUERY_PARAMS) is split off, and other sources are kept as-is.
+pub(crate) fn split_query(src: &str) -> (&str, Vec<(&str, &str)>) {
+    let Some((path, query)) = src.rsplit_once('?') else {
+        return (src, vec![]);
+    };
+    let params: Option<Vec<(&str, &str)>> = query
+        .split('&')
+        .map(|param| {
+            param
+                .split_once('=')
+                .filter(|(key, _)| QUERY_PARAMS.contains(key))
+        })
+        .collect();
+
+    match params {
+        Some(params) => (path, params),
+        None => (src, vec![]),
+    }
+}
+
+/// Get the options that an import's source and attributes override for that one import. A query on the source
+/// restricts its `metaFields` (`?meta=importedPath,relativeToGlob`) or selects a window of its sorted matches
+/// (`?limit=10&offset=20`), while attributes set options by name, e.g., `with { glob: "lazy", sort: "natural" }`,
+/// where the `glob` attribute selects a [Mode](crate::config::Mode). Like the values of directives, query and attribute
+/// values are parsed as JSON and fall back to a string, since both can only be strings.
+pub(crate) fn get_source_overrides(
+    src: &str,
+    attributes: Option<&ObjectLit>,
+) -> Map<String, Value> {
+    let mut overrides = Map::new();
+
+    split_query(src).1.into_iter().for_each(|(key, value)| {
+        if key == "meta" {
+            let fields: Vec<&str> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .collect();
+            overrides.insert("metaFields".into(), Value::from(fields));
+        } else {
+            overrides.insert(key.to_owned(), parse_value(value));
+        }
+    });
+
+    attributes
+        .into_iter()
+        .flat_map(|attributes| attributes.props.iter())
+        .filter_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                return None;
+            };
+            let Prop::KeyValue(KeyValueProp { key, value }) = &**prop else {
+                return None;
+            };
+            let key = match key {
+                PropName::Ident(ident) => ident.sym.to_string(),
+                PropName::Str(str) => str.value.to_string(),
+                _ => return None,
+            };
+            let Expr::Lit(Lit::Str(value)) = &**value else {
+                return None;
+            };
+            Some((key, value.value.to_string()))
+        })
+        .for_each(|(key, value)| match key.as_str() {
+            "glob" => {
+                overrides.insert("mode".into(), Value::from(value));
+            }
+            _ => {
+                overrides.insert(key, parse_value(&value));
+            }
+        });
+    overrides
+}
@@ -0,0 +1,87 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+
+/// Directories whose contents are never meant to be globbed over: installed dependencies and version control metadata.
+const JUNK_DIRS: [&str; 4] = [".git", ".hg", ".svn", "node_modules"];
+
+/// Extensions of TypeScript declaration files, which only describe types and can't be imported at runtime.
+const DECLARATION_EXTENSIONS: [&str; 3] = [".d.ts", ".d.mts", ".d.cts"];
+
+/// Directories that conventionally hold tests and mocks next to the code they cover.
+const TEST_DIRS: [&str; 2] = ["__mocks__", "__tests__"];
+
+/// Infixes of the file names of tests, e.g., `button.test.tsx`.
+const TEST_INFIXES: [&str; 2] = [".spec.", ".test."];
+
+/// A regular expression that matches are checked against after globbing, for exclusions that are awkward to express
+/// with a glob. A leading `!` inverts the filter, so matching paths are dropped instead of kept. The same filters check
+/// the contents of matches for `content_filter`.
+pub(crate) struct PathFilter {
+    negate: bool,
+    regex: Regex,
+}
+
+impl PathFilter {
+    pub(crate) fn new(filter: &str) -> Result<Self, regex::Error> {
+        let (negate, pattern) = match filter.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, filter),
+        };
+
+        Ok(PathFilter {
+            negate,
+            regex: Regex::new(pattern)?,
+        })
+    }
+
+    /// Check whether a match, given as its path relative to the glob's base directory, should be kept.
+    pub(crate) fn keep(&self, relative_to_glob: &Path) -> bool {
+        self.regex.is_match(&relative_to_glob.to_string_lossy()) != self.negate
+    }
+
+    /// Check whether a match, given as the path to the file, should be kept based on its contents. Files that can't be
+    /// read are treated as if they were empty.
+    pub(crate) fn keep_contents(&self, file_path: &Path) -> bool {
+        let contents = fs::read(file_path).unwrap_or_default();
+        self.regex.is_match(&String::from_utf8_lossy(&contents)) != self.negate
+    }
+}
+
+/// Check whether a match, given as its path relative to the glob's base directory, is a declaration file or lives in
+/// one of the [JUNK_DIRS](JUNK_DIRS), which are skipped unless `default_excludes` is turned off.
+pub(crate) fn is_junk_path(relative_to_glob: &Path) -> bool {
+    let is_declaration =
+        relative_to_glob
+            .file_name()
+            .and_then(OsStr::to_str)
+            .map_or(false, |name| {
+                DECLARATION_EXTENSIONS
+                    .iter()
+                    .any(|extension| name.ends_with(extension))
+            });
+
+    is_declaration
+        || relative_to_glob
+            .iter()
+            .any(|component| JUNK_DIRS.iter().any(|dir| component == *dir))
+}
+
+/// Check whether a match, given as its path relative to the glob's base directory, is a test or a mock, i.e., its file
+/// name has one of the [TEST_INFIXES](TEST_INFIXES) or it lives in one of the [TEST_DIRS](TEST_DIRS). Used when
+/// `exclude_tests` is set.
+pub(crate) fn is_test_path(relative_to_glob: &Path) -> bool {
+    let is_test_file = relative_to_glob
+        .file_name()
+        .and_then(OsStr::to_str)
+        .map_or(false, |name| {
+            TEST_INFIXES.iter().any(|infix| name.contains(infix))
+        });
+
+    is_test_file
+        || relative_to_glob
+            .iter()
+            .any(|component| TEST_DIRS.iter().any(|dir| component == *dir))
+}
@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+use swc_core::common::errors::HANDLER;
+
+/// Name of the file at the root of the project that holds the git metadata of its files. The plugin can't run `git`,
+/// and reading the last commit of a file from `.git` would mean inflating loose and packed objects, so this file has to
+/// be generated ahead of the build.
+pub(crate) const GIT_META_FILE_NAME: &str = ".import-glob-git.json";
+
+thread_local! {
+    /// The files of every git metadata file that was read, along with when it was last modified, so each is only parsed
+    /// again once it changes. Files that are missing, stale, or invalid are kept as `None`, so they are reported once.
+    static GIT_META_FILES: RefCell<HashMap<PathBuf, (Option<SystemTime>, Option<Map<String, Value>>)>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Get the `lastCommit` and `lastModified` of a match from the [GIT_META_FILE_NAME](GIT_META_FILE_NAME) at the root of
+/// the project, keyed by the match's path relative to that root. Matches outside of the project give back nothing,
+/// while matches without an entry are reported, since the file was generated before they were added.
+pub(crate) fn get_git_meta(project_root: &Path, file_path: &Path) -> Map<String, Value> {
+    let Some(relative_path) = file_path
+        .strip_prefix(project_root)
+        .ok()
+        .and_then(Path::to_str)
+    else {
+        return Map::new();
+    };
+    let relative_path = relative_path.replace('\\', "/");
+    let path = project_root.join(GIT_META_FILE_NAME);
+
+    GIT_META_FILES.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let is_fresh = cache
+            .get(&path)
+            .map_or(false, |(cached_modified, _)| *cached_modified == modified);
+        if !is_fresh {
+            let files = read_git_meta_file(project_root, &path)
+                .map_err(|err| {
+                    HANDLER.with(|handler| {
+                        handler.warn(&format!(
+                            "Import Glob Array Plugin ignored gitMeta, as {} {err}",
+                            path.display()
+                        ))
+                    })
+                })
+                .ok();
+            cache.insert(path.to_owned(), (modified, files));
+        }
+
+        let Some(files) = cache.get(&path).and_then(|(_, files)| files.as_ref()) else {
+            return Map::new();
+        };
+        match files.get(&relative_path).and_then(Value::as_object) {
+            Some(entry) => entry
+                .iter()
+                .filter(|(field, _)| *field == "lastCommit" || *field == "lastModified")
+                .map(|(field, value)| (field.to_owned(), value.to_owned()))
+                .collect(),
+            None => {
+                HANDLER.with(|handler| {
+                    handler.warn(&format!(
+                        "Import Glob Array Plugin found no git metadata for {relative_path} in {}, so it needs to be \
+                         generated again",
+                        path.display()
+                    ))
+                });
+                Map::new()
+            }
+        }
+    })
+}
+
+/// Read the files of the git metadata file at `path`, i.e., `{ "head": "...", "files": { "docs/intro.md": {
+/// "lastCommit": "...", "lastModified": 1700000000000 } } }`, checking its `head`, when it has one, against the commit
+/// that the repository at `project_root` is at.
+fn read_git_meta_file(project_root: &Path, path: &Path) -> Result<Map<String, Value>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't be read ({err}), so it needs to be generated first"))?;
+    let Value::Object(mut git_meta) =
+        serde_json::from_str(&contents).map_err(|err| format!("isn't valid JSON ({err})"))?
+    else {
+        return Err("doesn't contain a JSON object".into());
+    };
+
+    let head = git_meta.get("head").and_then(Value::as_str);
+    if let (Some(head), Some(repo_head)) = (head, read_git_head(project_root)) {
+        if head != repo_head {
+            return Err(format!(
+                "was generated at commit {head}, but the repository is at {repo_head}, so it needs to be generated \
+                 again"
+            ));
+        }
+    }
+
+    match git_meta.remove("files") {
+        Some(Value::Object(files)) => Ok(files),
+        _ => Err("doesn't have a `files` object".into()),
+    }
+}
+
+/// Get the commit that the git repository at `dir` is at, by following its `HEAD` through its loose or packed refs.
+/// Directories without a repository give back `None`.
+fn read_git_head(dir: &Path) -> Option<String> {
+    let git_dir = dir.join(".git");
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let Some(ref_name) = head.trim().strip_prefix("ref: ") else {
+        return Some(head.trim().to_owned());
+    };
+
+    if let Ok(commit) = fs::read_to_string(git_dir.join(ref_name)) {
+        return Some(commit.trim().to_owned());
+    }
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let (commit, name) = line.split_once(' ')?;
+            (name == ref_name).then(|| commit.to_owned())
+        })
+}
@@ -0,0 +1,170 @@
+use swc_core::common::errors::HANDLER;
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::{ArrayLit, Expr, ExprOrSpread};
+
+use crate::config::{Config, KeyCollision, KeyedOutput};
+use crate::template::render_template;
+use crate::utils::{get_new_expr, get_object_expr, get_str_expr};
+use crate::ImportPaths;
+
+type Elems<'a> = Vec<(&'a ImportPaths, Option<ExprOrSpread>)>;
+
+enum Group<'a> {
+    Elems(Elems<'a>),
+    Groups(Vec<(String, Group<'a>)>),
+}
+
+impl<'a> Group<'a> {
+    fn insert(
+        &mut self,
+        keys: &[String],
+        import_paths: &'a ImportPaths,
+        elem: Option<ExprOrSpread>,
+    ) {
+        match (self, keys.split_first()) {
+            (Group::Elems(elems), _) => elems.push((import_paths, elem)),
+            (Group::Groups(groups), Some((key, keys))) => {
+                let index = match groups.iter().position(|(existing, _)| existing == key) {
+                    Some(index) => index,
+                    None => {
+                        let group = if keys.is_empty() {
+                            Group::Elems(vec![])
+                        } else {
+                            Group::Groups(vec![])
+                        };
+                        groups.push((key.to_owned(), group));
+                        groups.len() - 1
+                    }
+                };
+                groups[index].1.insert(keys, import_paths, elem);
+            }
+            (Group::Groups(_), None) => {}
+        }
+    }
+
+    fn into_expr(self, config: &Config) -> Expr {
+        match self {
+            Group::Elems(elems) if config.keyed => get_keyed_expr(config, elems),
+            Group::Elems(elems) => Expr::Array(ArrayLit {
+                elems: elems.into_iter().map(|(_, elem)| elem).collect(),
+                span: DUMMY_SP,
+            }),
+            Group::Groups(groups) => {
+                let (keys, groups): (Vec<String>, Vec<Group>) = groups.into_iter().unzip();
+                get_object_expr(
+                    keys.iter()
+                        .map(String::as_str)
+                        .zip(groups.into_iter().map(|group| group.into_expr(config)))
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Collect elements into an object (or a `Map`, depending on `keyed_output`) keyed by the rendered `key_template` of
+/// their match, where matches whose key is already taken are handled according to `key_collision`. The `key_prefix` and
+/// `key_suffix` are added once every key is settled.
+fn get_keyed_expr(config: &Config, elems: Elems) -> Expr {
+    let mut props: Vec<(String, &ImportPaths, Expr)> = vec![];
+
+    elems.into_iter().for_each(|(import_paths, elem)| {
+        let Some(elem) = elem else {
+            return;
+        };
+        let key = render_template(&config.key_template, &import_paths.relative_to_glob);
+        let Some(index) = props.iter().position(|(existing, _, _)| *existing == key) else {
+            props.push((key, import_paths, *elem.expr));
+            return;
+        };
+
+        match config.key_collision {
+            KeyCollision::Error => HANDLER.with(|handler| {
+                handler.err(&format!(
+                    "{} and {} both have the key `{key}`",
+                    props[index].1.absolute_path, import_paths.absolute_path
+                ))
+            }),
+            KeyCollision::LastWins => props[index] = (key, import_paths, *elem.expr),
+            KeyCollision::Suffix => {
+                let key = (2..)
+                    .map(|suffix| format!("{key}-{suffix}"))
+                    .find(|key| props.iter().all(|(existing, _, _)| existing != key))
+                    .unwrap_or(key);
+                props.push((key, import_paths, *elem.expr));
+            }
+        }
+    });
+
+    props
+        .iter_mut()
+        .for_each(|(key, _, _)| *key = format!("{}{key}{}", config.key_prefix, config.key_suffix));
+
+    if config.keyed_output == KeyedOutput::Map {
+        let entries = props
+            .into_iter()
+            .map(|(key, _, expr)| {
+                Some(ExprOrSpread::from(Box::new(Expr::Array(ArrayLit {
+                    elems: vec![
+                        Some(ExprOrSpread::from(Box::new(get_str_expr(key)))),
+                        Some(ExprOrSpread::from(Box::new(expr))),
+                    ],
+                    span: DUMMY_SP,
+                }))))
+            })
+            .collect();
+        return get_new_expr(
+            "Map",
+            vec![Expr::Array(ArrayLit {
+                elems: entries,
+                span: DUMMY_SP,
+            })],
+        );
+    }
+
+    let (keys, exprs): (Vec<String>, Vec<Expr>) =
+        props.into_iter().map(|(key, _, expr)| (key, expr)).unzip();
+    get_object_expr(keys.iter().map(String::as_str).zip(exprs).collect())
+}
+
+/// Shape the elements that were generated for each match into the expression that is assigned to a binding. Matches
+/// are nested into objects keyed by the first `group_depth` directories of their path relative to the glob's base
+/// directory, e.g., `{ en: { common: [...] } }` for `en/common/buttons.json` with a depth of `2`, and are collected into
+/// an array at the innermost level, or into an object keyed by `key_template` when `keyed` is set. Matches that don't
+/// have enough directories are reported and skipped.
+pub(crate) fn get_grouped_expr(
+    config: &Config,
+    matches: &[ImportPaths],
+    elems: Vec<Option<ExprOrSpread>>,
+) -> Expr {
+    let depth = config.group_depth;
+    let mut root = if depth == 0 {
+        Group::Elems(vec![])
+    } else {
+        Group::Groups(vec![])
+    };
+
+    matches.iter().zip(elems).for_each(|(import_paths, elem)| {
+        let keys: Vec<String> = import_paths
+            .relative_to_glob
+            .parent()
+            .into_iter()
+            .flat_map(|dir| dir.iter())
+            .take(depth)
+            .map(|segment| segment.to_string_lossy().into_owned())
+            .collect();
+
+        if keys.len() < depth {
+            HANDLER.with(|handler| {
+                handler.err(&format!(
+                    "{} can't be grouped, since it isn't nested {depth} directories deep",
+                    import_paths.absolute_path
+                ))
+            });
+            return;
+        }
+        root.insert(&keys, import_paths, elem);
+    });
+
+    root.into_expr(config)
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use swc_core::common::{Mark, SyntaxContext};
+use swc_core::ecma::ast::{
+    ExportNamedSpecifier, Ident, ImportNamedSpecifier, MemberProp, Module, PropName,
+};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+use crate::transformer::{RUNTIME_PICK_NAME, RUNTIME_WRAP_IMPORT_ERROR_NAME};
+use crate::utils::{CREATE_REQUIRE_IMPORT_NAME, CREATE_REQUIRE_NAME};
+
+/// Local names of the helpers that the plugin imports or declares, which are generated like placeholders are.
+const HELPER_NAMES: [&str; 4] = [
+    CREATE_REQUIRE_IMPORT_NAME,
+    CREATE_REQUIRE_NAME,
+    RUNTIME_PICK_NAME,
+    RUNTIME_WRAP_IMPORT_ERROR_NAME,
+];
+
+/// Moves the identifiers of generated bindings into their own syntax context. Only identifiers that are still in the
+/// empty context are moved, so user-written identifiers of the same name that were already resolved keep theirs, and
+/// property names and exported names are left alone, since they aren't bindings.
+struct GeneratedIdentMarker<'a> {
+    ctxt: SyntaxContext,
+    names: &'a HashSet<String>,
+}
+
+impl VisitMut for GeneratedIdentMarker<'_> {
+    fn visit_mut_ident(&mut self, ident: &mut Ident) {
+        if ident.span.ctxt == SyntaxContext::empty()
+            && (self.names.contains(&*ident.sym) || HELPER_NAMES.contains(&&*ident.sym))
+        {
+            ident.span.ctxt = self.ctxt;
+        }
+    }
+
+    fn visit_mut_export_named_specifier(&mut self, specifier: &mut ExportNamedSpecifier) {
+        specifier.orig.visit_mut_with(self);
+    }
+
+    fn visit_mut_import_named_specifier(&mut self, specifier: &mut ImportNamedSpecifier) {
+        specifier.local.visit_mut_with(self);
+    }
+
+    fn visit_mut_member_prop(&mut self, prop: &mut MemberProp) {
+        if let MemberProp::Computed(computed) = prop {
+            computed.visit_mut_with(self);
+        }
+    }
+
+    fn visit_mut_prop_name(&mut self, name: &mut PropName) {
+        if let PropName::Computed(computed) = name {
+            computed.visit_mut_with(self);
+        }
+    }
+}
+
+/// Give the generated bindings of `module`, i.e., the placeholders in `names` and the plugin's helpers, a fresh
+/// [Mark](Mark), so hygiene-aware passes (such as a minifier's mangler) treat them as distinct from every identifier
+/// of the same name that a later transform introduces.
+pub(crate) fn mark_generated_idents(module: &mut Module, names: &HashSet<String>) {
+    let ctxt = SyntaxContext::empty().apply_mark(Mark::new());
+    module.visit_mut_with(&mut GeneratedIdentMarker { ctxt, names });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use swc_core::common::{Globals, Mark, SourceMap, GLOBALS};
+    use swc_core::ecma::ast::{
+        Decl, ExportDefaultExpr, Expr, ImportSpecifier, ModuleDecl, ModuleItem, Pat, Stmt,
+    };
+    use swc_core::ecma::transforms::base::hygiene::hygiene;
+    use swc_core::ecma::transforms::base::resolver;
+    use swc_core::ecma::visit::VisitMutWith;
+
+    use super::mark_generated_idents;
+    use crate::test_utils::parse_module;
+    use crate::transformer::RUNTIME_PICK_NAME;
+
+    /// A user binding of the same name as a helper, which a later transform introduces, doesn't capture the helper
+    /// once the resolver and hygiene passes run.
+    #[test]
+    fn helpers_do_not_collide_with_user_bindings() {
+        let cm = SourceMap::default();
+
+        GLOBALS.set(&Globals::new(), || {
+            let mut module = parse_module(
+                &cm,
+                &format!("import {RUNTIME_PICK_NAME} from \"./runtime.js\"; export default {RUNTIME_PICK_NAME};"),
+            );
+            mark_generated_idents(&mut module, &HashSet::new());
+            module
+                .body
+                .extend(parse_module(&cm, &format!("const {RUNTIME_PICK_NAME} = \"user\";")).body);
+
+            module.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), false));
+            module.visit_mut_with(&mut hygiene());
+
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &module.body[0] else {
+                panic!("expected an import");
+            };
+            let ImportSpecifier::Default(helper) = &import.specifiers[0] else {
+                panic!("expected a default import");
+            };
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr,
+                ..
+            })) = &module.body[1]
+            else {
+                panic!("expected a default export");
+            };
+            let Expr::Ident(exported) = &**expr else {
+                panic!("expected an identifier");
+            };
+            let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = &module.body[2] else {
+                panic!("expected a variable declaration");
+            };
+            let Pat::Ident(user_binding) = &var_decl.decls[0].name else {
+                panic!("expected an identifier");
+            };
+
+            assert_eq!(exported.sym, helper.local.sym);
+            assert_ne!(user_binding.id.sym, helper.local.sym);
+        });
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// How much of an image is read to find its dimensions, which covers the headers of every supported format, along with
+/// the metadata segments (such as EXIF thumbnails) that can come before the start-of-frame marker of a JPEG.
+const HEADER_LIMIT: u64 = 64 * 1024;
+
+/// Read the `(width, height)` of a PNG, GIF, JPEG, WebP, or BMP image from its header, without reading the rest of the
+/// image. Files in any other format (or that are truncated) give back `None`, as do JPEGs whose start-of-frame marker
+/// is past the first [HEADER_LIMIT](HEADER_LIMIT) bytes.
+pub(crate) fn get_image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut data = vec![];
+    File::open(path)
+        .ok()?
+        .take(HEADER_LIMIT)
+        .read_to_end(&mut data)
+        .ok()?;
+
+    match data.get(..4)? {
+        [0x89, b'P', b'N', b'G'] => Some((be_u32(&data, 16)?, be_u32(&data, 20)?)),
+        [b'G', b'I', b'F', b'8'] => Some((le_u16(&data, 6)?.into(), le_u16(&data, 8)?.into())),
+        [0xFF, 0xD8, 0xFF, _] => get_jpeg_dimensions(&data),
+        [b'R', b'I', b'F', b'F'] if data.get(8..12)? == b"WEBP" => get_webp_dimensions(&data),
+        [b'B', b'M', _, _] => Some((
+            le_u32(&data, 18)?,
+            (le_u32(&data, 22)? as i32).unsigned_abs(),
+        )),
+        _ => None,
+    }
+}
+
+/// Walk the segments of a JPEG until the first start-of-frame marker, which holds the image's dimensions.
+fn get_jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+
+    loop {
+        if *data.get(offset)? != 0xFF {
+            return None;
+        }
+
+        match *data.get(offset + 1)? {
+            0xFF => offset += 1,
+            0x01 | 0xD0..=0xD7 => offset += 2,
+            0xC0..=0xCF if !matches!(data[offset + 1], 0xC4 | 0xC8 | 0xCC) => {
+                return Some((
+                    be_u16(data, offset + 7)?.into(),
+                    be_u16(data, offset + 5)?.into(),
+                ));
+            }
+            _ => offset += 2 + usize::from(be_u16(data, offset + 2)?),
+        }
+    }
+}
+
+/// Read the dimensions of a lossy (`VP8 `), lossless (`VP8L`), or extended (`VP8X`) WebP image.
+fn get_webp_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    match data.get(12..16)? {
+        b"VP8 " => Some((
+            u32::from(le_u16(data, 26)? & 0x3FFF),
+            u32::from(le_u16(data, 28)? & 0x3FFF),
+        )),
+        b"VP8L" => {
+            let bits = le_u32(data, 21)?;
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => Some((le_u24(data, 24)? + 1, le_u24(data, 27)? + 1)),
+        _ => None,
+    }
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn le_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+fn le_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
@@ -1,4 +1,11 @@
-use swc_core::ecma::ast::{ImportSpecifier as SWCImportSpecifier, ModuleExportName};
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::{
+    ExportNamedSpecifier, ExportSpecifier, Ident, ImportDecl, ImportDefaultSpecifier,
+    ImportNamedSpecifier, ImportSpecifier as SWCImportSpecifier, ImportStarAsSpecifier,
+    ModuleExportName, Pat,
+};
+
+use crate::utils::get_ident_pat;
 
 pub(crate) struct ImportSpecifier(SWCImportSpecifier);
 
@@ -13,6 +20,42 @@ impl ImportSpecifier {
         }
     }
 
+    /// Get a key for the binding that the specifier receives, so that specifiers of different imports that receive the
+    /// same binding can be told apart from the rest, e.g., `default`, the name of a named export, `*` for namespace
+    /// specifiers, or `_importMeta`.
+    pub(crate) fn get_binding_key(&self) -> String {
+        if self.is_meta_decl().unwrap_or(false) {
+            return IMPORT_META_NAME.into();
+        }
+        self.get_export_name().unwrap_or_else(|| "*".into())
+    }
+
+    /// Get the name of the module export that the specifier binds, i.e., `default` for default specifiers. Namespace
+    /// specifiers bind the whole module, so they give back `None`.
+    pub(crate) fn get_export_name(&self) -> Option<String> {
+        match &self.0 {
+            SWCImportSpecifier::Default(_) => Some("default".into()),
+            SWCImportSpecifier::Named(_) => self.get_imported_name(),
+            SWCImportSpecifier::Namespace(_) => None,
+        }
+    }
+
+    /// Get the name of the module export that the specifier imports, falling back to its local name when it isn't
+    /// renamed. Default and namespace specifiers don't import a named export, so they give back `None`.
+    pub(crate) fn get_imported_name(&self) -> Option<String> {
+        let named_specifier = (&self.0).to_owned().named()?;
+
+        match named_specifier.imported {
+            Some(ModuleExportName::Ident(ident)) => Some(ident.sym.to_string()),
+            Some(ModuleExportName::Str(str)) => Some(str.value.to_string()),
+            None => Some(named_specifier.local.sym.to_string()),
+        }
+    }
+
+    pub(crate) fn get_local_pat(&self) -> Pat {
+        get_ident_pat(&self.get_local_name())
+    }
+
     pub(crate) fn is_meta_decl(&self) -> Option<bool> {
         let named_specifier = (&self.0).to_owned().named()?;
         let export_name = named_specifier.imported?;
@@ -39,3 +82,96 @@ impl From<SWCImportSpecifier> for ImportSpecifier {
         ImportSpecifier(value)
     }
 }
+
+/// Get the import specifier that is equivalent to a specifier of a glob re-export, along with the specifier that exports
+/// its local binding under the requested name, e.g., `export * as docs from "./docs/*.md"` becomes
+/// `import * as docs from "./docs/*.md"` and `export { docs }`. Specifiers that are exported under a string name give
+/// back `None`.
+pub(crate) fn get_reexport_specifiers(
+    specifier: &ExportSpecifier,
+) -> Option<(SWCImportSpecifier, ExportNamedSpecifier)> {
+    let (import_specifier, local) = match specifier {
+        ExportSpecifier::Default(default) => (
+            SWCImportSpecifier::Default(ImportDefaultSpecifier {
+                local: default.exported.clone(),
+                span: DUMMY_SP,
+            }),
+            default.exported.clone(),
+        ),
+        ExportSpecifier::Namespace(namespace) => {
+            let ModuleExportName::Ident(local) = &namespace.name else {
+                return None;
+            };
+            (
+                SWCImportSpecifier::Namespace(ImportStarAsSpecifier {
+                    local: local.clone(),
+                    span: DUMMY_SP,
+                }),
+                local.clone(),
+            )
+        }
+        ExportSpecifier::Named(named) => {
+            let exported = named.exported.as_ref().unwrap_or(&named.orig);
+            let ModuleExportName::Ident(local) = exported else {
+                return None;
+            };
+            (
+                SWCImportSpecifier::Named(ImportNamedSpecifier {
+                    imported: Some(named.orig.clone()),
+                    is_type_only: false,
+                    local: local.clone(),
+                    span: DUMMY_SP,
+                }),
+                local.clone(),
+            )
+        }
+    };
+
+    Some((
+        import_specifier,
+        ExportNamedSpecifier {
+            exported: None,
+            is_type_only: false,
+            orig: ModuleExportName::Ident(Ident::new(local.sym, DUMMY_SP)),
+            span: DUMMY_SP,
+        },
+    ))
+}
+
+/// Print an import declaration back to source, e.g., `import docs, { _importMeta as meta } from "./docs/*.md";`, so the
+/// original glob import can be kept as a comment in the output.
+pub(crate) fn get_import_source(import_decl: &ImportDecl) -> String {
+    let src = serde_json::to_string(&*import_decl.src.value).unwrap_or_default();
+    let mut clauses: Vec<String> = vec![];
+    let mut named: Vec<String> = vec![];
+
+    import_decl
+        .specifiers
+        .iter()
+        .for_each(|specifier| match specifier {
+            SWCImportSpecifier::Default(default) => clauses.push(default.local.sym.to_string()),
+            SWCImportSpecifier::Namespace(as_star) => {
+                clauses.push(format!("* as {}", as_star.local.sym))
+            }
+            SWCImportSpecifier::Named(named_specifier) => {
+                let local = &named_specifier.local.sym;
+                named.push(match &named_specifier.imported {
+                    Some(ModuleExportName::Ident(ident)) => format!("{} as {local}", ident.sym),
+                    Some(ModuleExportName::Str(str)) => format!(
+                        "{} as {local}",
+                        serde_json::to_string(&*str.value).unwrap_or_default()
+                    ),
+                    None => local.to_string(),
+                })
+            }
+        });
+
+    if !named.is_empty() {
+        clauses.push(format!("{{ {} }}", named.join(", ")));
+    }
+    if clauses.is_empty() {
+        format!("import {src};")
+    } else {
+        format!("import {} from {src};", clauses.join(", "))
+    }
+}
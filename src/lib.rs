@@ -1,114 +1,1579 @@
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::Instant;
 
+use glob::{glob, Pattern, PatternError};
 use is_glob::is_glob;
-use swc_core::ecma::ast::{Decl, ImportDecl, Module, ModuleDecl, ModuleItem, Stmt};
+use serde_json::{json, Map, Value};
+use swc_core::common::comments::{CommentKind, Comments};
+use swc_core::common::errors::{SourceMapperDyn, HANDLER};
+use swc_core::common::sync::Lrc;
+use swc_core::common::{Span, Spanned, DUMMY_SP};
+use swc_core::ecma::ast::{
+    ArrayLit, CallExpr, Callee, Decl, ExportAll, ExportDecl, ExportNamespaceSpecifier,
+    ExportSpecifier, Expr, ExprOrSpread, Ident, ImportDefaultSpecifier,
+    ImportSpecifier as SWCImportSpecifier, ImportStarAsSpecifier, Lit, MemberExpr, MemberProp,
+    MetaPropKind, Module, ModuleDecl, ModuleExportName, ModuleItem, NamedExport, Stmt, Str,
+    TsExternalModuleRef, TsImportEqualsDecl, TsModuleRef, VarDecl, VarDeclKind,
+};
 use swc_core::ecma::visit::Fold;
 use swc_core::ecma::{ast::Program, visit::FoldWith};
-use swc_core::plugin::metadata::TransformPluginMetadataContextKind::{Cwd, Filename};
+use swc_core::plugin::metadata::TransformPluginMetadataContextKind::{Cwd, Env, Filename};
 use swc_core::plugin::{plugin_transform, proxies::TransformPluginProgramMetadata};
+use tracing::instrument;
 
-use crate::transformer::{transform_import_decl, TransformedStatements};
+use crate::analyze::{collect_glob_imports, write_analysis_entry};
+use crate::cache::GlobCache;
+use crate::config::{
+    Compat, Config, ContextDependencies, MatchReport, NamespaceReexports, Prefetch, SpecifierStyle,
+    Target,
+};
+use crate::directives::{
+    get_directive_overrides, get_meta_augmentation, get_source_overrides, split_query,
+};
+use crate::hygiene::mark_generated_idents;
+use crate::imports::{get_import_source, get_reexport_specifiers, ImportSpecifier};
+use crate::manifest::{
+    get_globbed_dirs, write_editor_index_entry, write_manifest_entry, write_watch_manifest_entry,
+    EDITOR_INDEX_FILE_NAME, MANIFEST_SPECIFIER,
+};
+use crate::packages::is_package_pattern;
+use crate::stamps::update_stamp_entry;
+use crate::stats::{count_nodes, write_stats_entry, FileStats};
+use crate::template::render_template;
+use crate::transformer::{get_matches, transform_import_decl, TransformedStatements};
+use crate::usage::get_referenced_names;
+use crate::utils::{
+    add_leading_comment, add_span_comment, collapse_index_path, get_call_stmt,
+    get_create_require_items, get_dynamic_import_expr, get_file_url, get_ident_array_expr,
+    get_ident_expr, get_ident_pat, get_named_import_decl, get_normalized_path, get_real_path,
+    get_relative_path, get_str_expr, get_var_decl, is_ident, to_nfc,
+};
 
+mod analyze;
+mod cache;
+mod config;
+mod directives;
+mod filter;
+mod git;
+mod group;
+mod hygiene;
+mod image;
 mod imports;
+mod manifest;
+mod meta;
+mod packages;
+mod routes;
+mod stamps;
+mod stats;
+mod template;
+#[cfg(test)]
+mod test_utils;
 mod transformer;
+mod usage;
 mod utils;
 
+pub use crate::analyze::{GlobImport, GlobMatch};
+
+/// Scheme that explicitly marks an import's source as a glob pattern, e.g., `glob:./modules/*.ts`.
+const GLOB_SCHEME: &str = "glob:";
+
+/// Comment that makes the plugin skip a file entirely when it's at the top of the file.
+const DISABLE_DIRECTIVE: &str = "import-glob-array-disable";
+
+/// Name of the files that customize the plugin's config for every file in their directory and below.
+const RC_FILE_NAME: &str = ".importglobrc.json";
+
 #[derive(Debug)]
-struct ImportGlobArrayPlugin {
+struct ImportGlobArrayPlugin<C: Comments> {
+    comments: Option<C>,
+    config: Config,
     cwd: PathBuf,
+    duplicate_locals: HashSet<String>,
+    env: Option<String>,
+    expanded_bindings: Rc<RefCell<HashMap<String, String>>>,
     filename: PathBuf,
+    generated_names: Rc<RefCell<HashSet<String>>>,
+    glob_cache: Rc<RefCell<GlobCache>>,
     id_counter: Rc<RefCell<usize>>,
+    index_entries: Rc<RefCell<Vec<Value>>>,
+    manifest_patterns: Rc<RefCell<Map<String, Value>>>,
+    match_counts: Rc<RefCell<Vec<usize>>>,
+    merged_bindings: Rc<RefCell<Vec<MergedBinding>>>,
+    named_imports: Rc<RefCell<Vec<(String, String, String)>>>,
+    needs_create_require: Rc<RefCell<bool>>,
+    referenced_names: HashSet<String>,
+    source_map: Option<SourceMap>,
+    unindexed_matches: Rc<RefCell<Vec<(String, Vec<Value>)>>>,
+    watched_dirs: Rc<RefCell<BTreeSet<String>>>,
+}
+
+/// A binding that collects the matches of several glob imports, which is declared after the last of them.
+#[derive(Debug)]
+struct MergedBinding {
+    flatten: bool,
+    kind: VarDeclKind,
+    last_index: usize,
+    name: String,
+    parts: Vec<String>,
+}
+
+/// Source map of the file being transformed, which locates the spans of glob imports for the editor index.
+struct SourceMap(Lrc<SourceMapperDyn>);
+
+impl fmt::Debug for SourceMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SourceMap")
+    }
 }
 
 #[derive(Debug)]
 struct ImportPaths {
     absolute_path: String,
+    file_path: PathBuf,
     imported_path: String,
+    relative_to_glob: PathBuf,
 }
 
-impl ImportGlobArrayPlugin {
-    fn build_module_items(&self, transformed: Option<TransformedStatements>) -> Vec<ModuleItem> {
+impl<C: Comments> ImportGlobArrayPlugin<C> {
+    fn build_module_items(
+        &self,
+        config: &Config,
+        transformed: Option<TransformedStatements>,
+    ) -> Vec<ModuleItem> {
         let mut results: Vec<ModuleItem> = vec![];
 
         if let Some(transformed) = transformed {
             let TransformedStatements {
+                context_dependencies,
+                exports,
                 imports,
+                lookups,
                 names,
                 meta,
+                requires,
+                types,
+                warnings,
             } = transformed;
+            let kind = VarDeclKind::from(config.declaration_kind);
+            let with_kind = |item: VarDecl| Box::new(VarDecl { kind, ..item });
 
             imports
                 .into_iter()
                 .for_each(|item| results.push(ModuleItem::ModuleDecl(ModuleDecl::Import(item))));
 
+            requires.into_iter().for_each(|item| {
+                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(with_kind(item)))))
+            });
+
             names.into_iter().for_each(|item| {
-                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(item)))))
+                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(with_kind(item)))))
             });
 
             meta.into_iter().for_each(|item| {
-                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(item)))))
+                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(with_kind(item)))))
             });
+
+            lookups
+                .into_iter()
+                .for_each(|item| results.push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(item)))));
+
+            types.into_iter().for_each(|item| {
+                results.push(ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(Box::new(
+                    item,
+                )))))
+            });
+
+            exports.into_iter().for_each(|item| {
+                results.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(with_kind(item)),
+                    span: DUMMY_SP,
+                })))
+            });
+
+            warnings
+                .into_iter()
+                .for_each(|item| results.push(ModuleItem::Stmt(item)));
+
+            match config.context_dependencies {
+                ContextDependencies::None => {}
+                ContextDependencies::Comment => {
+                    if let Some(first) = results.first_mut() {
+                        context_dependencies.iter().for_each(|dir| {
+                            add_leading_comment(
+                                &self.comments,
+                                first,
+                                CommentKind::Block,
+                                format!(" import-glob-array-context: {dir} "),
+                            )
+                        });
+                    }
+                }
+                ContextDependencies::Call => results.push(ModuleItem::Stmt(get_call_stmt(
+                    &config.context_dependency_callee,
+                    context_dependencies,
+                ))),
+            }
         }
         results
     }
 
-    fn get_paths(&self, path: &PathBuf) -> Option<ImportPaths> {
-        let path = self.cwd.join(if path.starts_with("/cwd") {
-            path.strip_prefix("/cwd").ok()?
-        } else {
-            path
+    /// Expand an `export * from "./x/*.js"` declaration into an `export *` declaration for every match.
+    fn expand_export_all(&self, export_all: ExportAll, pattern: &str) -> Vec<ModuleItem> {
+        let config = self.get_import_config(export_all.span);
+
+        match get_matches(self, &config, pattern) {
+            Some((matches, _)) => matches
+                .iter()
+                .map(|import_paths| {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+                        span: DUMMY_SP,
+                        src: Box::new(Str {
+                            raw: None,
+                            span: DUMMY_SP,
+                            value: import_paths.imported_path.to_owned().into(),
+                        }),
+                        ..export_all.clone()
+                    }))
+                })
+                .collect(),
+            None => vec![ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all))],
+        }
+    }
+
+    /// Expand a TypeScript `import handlers = require("./handlers/*.ts")` declaration into an import-equals declaration
+    /// for every match, and an array of their placeholders that is assigned to the original name.
+    fn expand_import_equals(
+        &self,
+        import_equals: TsImportEqualsDecl,
+        pattern: &str,
+    ) -> Vec<ModuleItem> {
+        let config = self.get_import_config(import_equals.span);
+        let Some((matches, _)) = get_matches(self, &config, pattern) else {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(
+                Box::new(import_equals),
+            ))];
+        };
+
+        let mut items: Vec<ModuleItem> = vec![];
+        let mut elems: Vec<Option<ExprOrSpread>> = vec![];
+
+        matches.iter().for_each(|import_paths| {
+            let placeholder = self.next_id(&config, "_iga");
+            items.push(ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(
+                Box::new(TsImportEqualsDecl {
+                    id: Ident::new(placeholder.as_str().into(), DUMMY_SP),
+                    module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef {
+                        expr: Str {
+                            raw: None,
+                            span: DUMMY_SP,
+                            value: import_paths.imported_path.to_owned().into(),
+                        },
+                        span: DUMMY_SP,
+                    }),
+                    span: DUMMY_SP,
+                    ..import_equals.clone()
+                }),
+            )));
+            elems.push(Some(ExprOrSpread::from(get_ident_expr(&placeholder))));
         });
-        let relative_path = path.strip_prefix(&self.cwd).ok()?.to_str()?.to_owned();
-        let absolute_path = self.cwd.join(&relative_path).to_str()?.to_owned();
-        let imported_path = if relative_path.starts_with('.') {
-            relative_path.to_owned()
+
+        items.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            kind: config.declaration_kind.into(),
+            ..get_var_decl(
+                get_ident_pat(&import_equals.id.sym),
+                Expr::Array(ArrayLit {
+                    elems,
+                    span: DUMMY_SP,
+                }),
+            )
+        })))));
+        items
+    }
+
+    /// Expand a glob import with its effective `config` into the statements that replace it, sharing any bindings that
+    /// an earlier import of the same pattern already generated. With `drop_unused`, bindings that the module never
+    /// references are left out, and an import whose bindings are all unused is dropped entirely.
+    fn expand_import(
+        &self,
+        config: Config,
+        pattern: &str,
+        mut specifiers: Vec<SWCImportSpecifier>,
+    ) -> Vec<ModuleItem> {
+        if config.drop_unused && !specifiers.is_empty() {
+            specifiers = specifiers
+                .into_iter()
+                .map(ImportSpecifier::from)
+                .filter(|specifier| {
+                    let local = specifier.get_local_name();
+                    self.referenced_names.contains(&local) || self.is_merged_part(&local)
+                })
+                .map(ImportSpecifier::into_inner)
+                .collect();
+            if specifiers.is_empty() {
+                return vec![];
+            }
+        }
+        let (mut items, specifiers) = self.share_expanded_bindings(&config, pattern, specifiers);
+
+        if !specifiers.is_empty() {
+            items.splice(
+                0..0,
+                self.build_module_items(
+                    &config,
+                    transform_import_decl(self, &config, pattern, specifiers),
+                ),
+            );
+        }
+        items
+    }
+
+    /// Expand an `export * as handlers from "./handlers/*.ts"` re-export into a namespace re-export for every match,
+    /// named after the rendered `key_template` of the match. Names that aren't identifiers are exported as strings, and
+    /// matches whose name is already taken are reported and skipped.
+    fn expand_namespace_reexports(
+        &self,
+        config: &Config,
+        named_export: NamedExport,
+        pattern: &str,
+    ) -> Vec<ModuleItem> {
+        let Some((matches, _)) = get_matches(self, config, pattern) else {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                named_export,
+            ))];
+        };
+        let mut exported_names: Vec<String> = vec![];
+
+        matches
+            .iter()
+            .filter_map(|import_paths| {
+                let name = render_template(&config.key_template, &import_paths.relative_to_glob);
+                if exported_names.contains(&name) {
+                    report_error(
+                        named_export.span,
+                        &format!(
+                            "{} can't be re-exported as {name}, which is already exported by another match",
+                            import_paths.absolute_path
+                        ),
+                    );
+                    return None;
+                }
+                exported_names.push(name.to_owned());
+
+                let name = if is_ident(&name) {
+                    ModuleExportName::Ident(Ident::new(name.into(), DUMMY_SP))
+                } else {
+                    ModuleExportName::Str(Str {
+                        raw: None,
+                        span: DUMMY_SP,
+                        value: name.into(),
+                    })
+                };
+                Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                    NamedExport {
+                        asserts: None,
+                        span: DUMMY_SP,
+                        specifiers: vec![ExportSpecifier::Namespace(ExportNamespaceSpecifier {
+                            name,
+                            span: DUMMY_SP,
+                        })],
+                        src: Some(Box::new(Str {
+                            raw: None,
+                            span: DUMMY_SP,
+                            value: import_paths.imported_path.to_owned().into(),
+                        })),
+                        type_only: false,
+                    },
+                )))
+            })
+            .collect()
+    }
+
+    /// Expand a glob re-export, such as `export * as docs from "./docs/*.md"` or `export docs from "./docs/*.md"`, the
+    /// same way as the equivalent import, and export the generated bindings under the requested names. Re-exports
+    /// that use string names are left untouched. With the [PerMatch](NamespaceReexports::PerMatch)
+    /// `namespace_reexports`, a lone `export * as` re-exports every match as its own namespace instead.
+    fn expand_reexport(&self, named_export: NamedExport, pattern: &str) -> Vec<ModuleItem> {
+        let overrides = named_export
+            .src
+            .as_ref()
+            .map(|src| get_source_overrides(&src.value, named_export.asserts.as_deref()))
+            .unwrap_or_default();
+        let config = self.get_overridden_config(named_export.span, overrides);
+        if config.namespace_reexports == NamespaceReexports::PerMatch
+            && matches!(
+                named_export.specifiers.as_slice(),
+                [ExportSpecifier::Namespace(_)]
+            )
+        {
+            return self.expand_namespace_reexports(&config, named_export, pattern);
+        }
+
+        let Some((import_specifiers, export_specifiers)): Option<(Vec<_>, Vec<_>)> = named_export
+            .specifiers
+            .iter()
+            .map(get_reexport_specifiers)
+            .collect::<Option<Vec<_>>>()
+            .map(|specifiers| specifiers.into_iter().unzip())
+        else {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                named_export,
+            ))];
+        };
+
+        let mut items = self.expand_import(config, pattern, import_specifiers);
+        items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+            NamedExport {
+                asserts: None,
+                span: DUMMY_SP,
+                specifiers: export_specifiers
+                    .into_iter()
+                    .map(ExportSpecifier::Named)
+                    .collect(),
+                src: None,
+                type_only: false,
+            },
+        )));
+        items
+    }
+
+    /// Expand a `require.resolve("./migrations/*.sql")` or `import.meta.resolve("./plugins/*.js")` call into an array
+    /// of what the call resolves to for every match, in the same order as the matches of an import, i.e., the absolute
+    /// path of each match, or its `file:` URL for `import.meta.resolve()`. Nothing is imported, so this is meant for code
+    /// that needs the paths or URLs of files rather than their modules. With `preserve_resolve_calls`, every match gets
+    /// a call to the same resolver instead, which is left to the bundler or the runtime.
+    fn expand_resolve_call(&self, call: CallExpr, pattern: &str) -> Expr {
+        let config = self.get_import_config(call.span);
+        let Some((matches, _)) = get_matches(self, &config, pattern) else {
+            return Expr::Call(call);
+        };
+        let resolves_url = matches!(
+            &call.callee,
+            Callee::Expr(callee) if matches!(&**callee, Expr::Member(member) if member.obj.is_meta_prop())
+        );
+
+        Expr::Array(ArrayLit {
+            elems: matches
+                .iter()
+                .map(|import_paths| {
+                    let expr = if config.preserve_resolve_calls {
+                        Expr::Call(CallExpr {
+                            args: vec![ExprOrSpread::from(Box::new(get_str_expr(
+                                import_paths.imported_path.to_owned(),
+                            )))],
+                            span: DUMMY_SP,
+                            ..call.clone()
+                        })
+                    } else if resolves_url {
+                        get_str_expr(get_file_url(&import_paths.absolute_path))
+                    } else {
+                        get_str_expr(import_paths.absolute_path.to_owned())
+                    };
+                    Some(ExprOrSpread::from(Box::new(expr)))
+                })
+                .collect(),
+            span: call.span,
+        })
+    }
+
+    /// Get an [Expr](Expr) that dynamically imports a match, annotated with any configured bundler magic comments.
+    fn get_dynamic_import_expr(&self, config: &Config, import_paths: &ImportPaths) -> Expr {
+        let mut magic_comments: Vec<String> = vec![];
+
+        if let Some(chunk_name) = &config.chunk_name {
+            magic_comments.push(format!(
+                "webpackChunkName: \"{}\"",
+                render_template(chunk_name, &import_paths.relative_to_glob)
+            ));
+        }
+
+        match config.prefetch {
+            Prefetch::WebpackPrefetch => magic_comments.push("webpackPrefetch: true".into()),
+            Prefetch::WebpackPreload => magic_comments.push("webpackPreload: true".into()),
+            _ => {}
+        }
+
+        let mut src_span = DUMMY_SP;
+        if !magic_comments.is_empty() {
+            add_span_comment(
+                &self.comments,
+                &mut src_span,
+                CommentKind::Block,
+                format!(" {} ", magic_comments.join(", ")),
+            );
+        }
+        get_dynamic_import_expr(&import_paths.imported_path, src_span)
+    }
+
+    /// Get the glob pattern of a `require.resolve()` or `import.meta.resolve()` call whose only argument is a string literal, if it's a glob.
+    fn get_resolve_call_pattern(&self, call: &CallExpr) -> Option<String> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) = &**callee
+        else {
+            return None;
+        };
+        let is_resolver = match &**obj {
+            Expr::Ident(obj) => &*obj.sym == "require",
+            Expr::MetaProp(meta_prop) => meta_prop.kind == MetaPropKind::ImportMeta,
+            _ => false,
+        };
+        if !is_resolver || &*prop.sym != "resolve" {
+            return None;
+        }
+
+        match call.args.as_slice() {
+            [ExprOrSpread { spread: None, expr }] => match &**expr {
+                Expr::Lit(Lit::Str(src)) => self.get_glob_pattern(&src.value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Get the local names that the default or namespace specifiers of more than one glob import in `module` bind, whose
+    /// matches are merged into a single binding.
+    fn get_duplicate_locals(&self, module: &Module) -> HashSet<String> {
+        let mut locals: HashSet<String> = HashSet::new();
+        let mut duplicate_locals: HashSet<String> = HashSet::new();
+
+        module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => Some(import_decl),
+                _ => None,
+            })
+            .filter(|import_decl| {
+                self.get_glob_pattern(split_query(&import_decl.src.value).0)
+                    .is_some()
+            })
+            .flat_map(|import_decl| import_decl.specifiers.iter())
+            .filter_map(|specifier| match specifier {
+                SWCImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                | SWCImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                    Some(local.sym.to_string())
+                }
+                SWCImportSpecifier::Named(_) => None,
+            })
+            .for_each(|local| {
+                if !locals.insert(local.to_owned()) {
+                    duplicate_locals.insert(local);
+                }
+            });
+        duplicate_locals
+    }
+
+    /// Get the effective config of a glob import, import-equals declaration, or re-export, i.e., its import config with
+    /// the overrides of its source and attributes (see [get_source_overrides](get_source_overrides)) applied.
+    fn get_item_config(&self, item: &ModuleItem) -> Config {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => self.get_overridden_config(
+                import_decl.span,
+                get_source_overrides(&import_decl.src.value, import_decl.asserts.as_deref()),
+            ),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                asserts,
+                span,
+                src: Some(src),
+                ..
+            })) => self
+                .get_overridden_config(*span, get_source_overrides(&src.value, asserts.as_deref())),
+            _ => self.get_import_config(item.span()),
+        }
+    }
+
+    /// Get the glob pattern of a module item, if it's an import, import-equals declaration, or re-export whose source
+    /// should be expanded. See [get_glob_pattern](Self::get_glob_pattern).
+    fn get_item_glob_pattern(&self, item: &ModuleItem) -> Option<String> {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                self.get_glob_pattern(split_query(&import_decl.src.value).0)
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals))
+                if !import_equals.is_export && !import_equals.is_type_only =>
+            {
+                match &import_equals.module_ref {
+                    TsModuleRef::TsExternalModuleRef(module_ref) => {
+                        self.get_glob_pattern(&module_ref.expr.value)
+                    }
+                    TsModuleRef::TsEntityName(_) => None,
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                src: Some(src), ..
+            })) => self.get_glob_pattern(split_query(&src.value).0),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
+                self.get_glob_pattern(&export_all.src.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get where `span` starts and ends in the file being transformed, as 1-based lines and 0-based columns. Spans of
+    /// generated code, and spans in files without a source map, give back `null`.
+    fn get_location(&self, span: Span) -> Value {
+        let Some(source_map) = self.source_map.as_ref().filter(|_| !span.is_dummy()) else {
+            return Value::Null;
+        };
+        let get_position = |pos| {
+            let loc = source_map.0.lookup_char_pos(pos);
+            json!({ "line": loc.line, "column": loc.col.0 })
+        };
+
+        json!({ "start": get_position(span.lo), "end": get_position(span.hi) })
+    }
+
+    /// Get the glob pattern of an import's source, if the import should be expanded. Sources that opt in with the
+    /// `glob:` scheme are always expanded, while other relative, absolute, aliased, or bare package sources are expanded
+    /// when they look like a glob, unless `require_glob_scheme` is set. Either way, the `pattern_rewrites` are applied
+    /// to the source first.
+    fn get_glob_pattern(&self, src: &str) -> Option<String> {
+        if let Some(pattern) = src.strip_prefix(GLOB_SCHEME) {
+            return Some(self.config.rewrite_pattern(pattern));
+        }
+        let src = &*self.config.rewrite_pattern(src);
+
+        let is_path = src.starts_with('.')
+            || src.starts_with('/')
+            || self.resolve_path_alias(&self.config, src).is_some()
+            || is_package_pattern(src);
+
+        if !self.config.require_glob_scheme && is_path && is_glob(src) {
+            return Some(src.to_owned());
+        }
+        None
+    }
+
+    /// Get the effective config for the file being transformed, which is the plugin's config merged with every
+    /// `.importglobrc.json` from the project root down to the file's directory, so the nearest one takes precedence.
+    fn get_file_config(&self) -> Result<Config, String> {
+        let importer_dir = self.importer_dir();
+        let root = self.project_root();
+        let mut dirs: Vec<&Path> = importer_dir
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&root))
+            .collect();
+        dirs.reverse();
+
+        dirs.into_iter()
+            .map(|dir| dir.join(RC_FILE_NAME))
+            .filter(|rc_path| rc_path.is_file())
+            .try_fold(self.config.clone(), |config, rc_path| {
+                fs::read_to_string(&rc_path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|contents| {
+                        serde_json::from_str(&contents).map_err(|err| err.to_string())
+                    })
+                    .and_then(|overrides| config.merge(overrides))
+                    .map_err(|err| format!("{}: {err}", self.host_path(&rc_path).display()))
+            })
+    }
+
+    /// Get the effective config for the import at `span`, which is the plugin's config merged with any overrides from
+    /// an `// import-glob: ...` directive comment directly above it, and any `// glob-meta: {...}` comment trailing
+    /// it. Invalid comments are reported, and are otherwise ignored.
+    fn get_import_config(&self, span: Span) -> Config {
+        let Some(comments) = &self.comments else {
+            return self.config.clone();
+        };
+
+        let mut config = match comments
+            .get_leading(span.lo)
+            .and_then(|comments| get_directive_overrides(&comments))
+        {
+            Some(overrides) => self.config.merge(overrides).unwrap_or_else(|err| {
+                report_error(span, &format!("Invalid import-glob directive: {err}"));
+                self.config.clone()
+            }),
+            None => self.config.clone(),
+        };
+
+        match comments
+            .get_trailing(span.hi)
+            .and_then(|comments| get_meta_augmentation(&comments))
+        {
+            Some(Ok(meta)) => config.meta.extend(meta),
+            Some(Err(err)) => report_error(span, &format!("Invalid glob-meta comment: {err}")),
+            None => {}
+        }
+        config
+    }
+
+    /// Get the effective config for the import at `span`, with the `overrides` that its source and attributes carry
+    /// (see [get_source_overrides](get_source_overrides)) applied over any directive. Invalid overrides are reported,
+    /// and are otherwise ignored.
+    fn get_overridden_config(&self, span: Span, overrides: Map<String, Value>) -> Config {
+        let config = self.get_import_config(span);
+        if overrides.is_empty() {
+            return config;
+        }
+
+        config.merge(overrides).unwrap_or_else(|err| {
+            report_error(span, &format!("Invalid import attributes: {err}"));
+            config
+        })
+    }
+
+    /// Get every path that the glob at `glob_path`, whose base directory is `base`, matches, from the glob cache when
+    /// it's configured and still holds the glob's matches.
+    fn glob_paths(&self, glob_path: &str, base: &Path) -> Result<Vec<PathBuf>, PatternError> {
+        let Some(cache_path) = self.cache_path() else {
+            return Ok(glob(glob_path)?.filter_map(|result| result.ok()).collect());
+        };
+
+        let mut glob_cache = self.glob_cache.borrow_mut();
+        if let Some(paths) = glob_cache.get(&cache_path, glob_path) {
+            return Ok(paths);
+        }
+
+        let paths: Vec<PathBuf> = glob(glob_path)?.filter_map(|result| result.ok()).collect();
+        glob_cache.insert(glob_path, base, &paths);
+        Ok(paths)
+    }
+
+    fn get_paths(&self, config: &Config, path: &PathBuf, glob_base: &Path) -> Option<ImportPaths> {
+        let relative_to_glob = path.strip_prefix(glob_base).ok()?.to_path_buf();
+        let file_path = path.to_owned();
+        let path = self.host_path(path);
+        let absolute_path = if config.real_paths {
+            self.host_path(&get_real_path(&file_path))
+                .to_str()?
+                .to_owned()
         } else {
-            format!("./{relative_path}")
+            path.to_str()?.to_owned()
+        };
+        let imported_path = match config.specifier_style {
+            SpecifierStyle::Relative => {
+                let relative_path = get_relative_path(&self.host_path(&self.importer_dir()), &path);
+                let relative_path = relative_path.to_str()?;
+
+                if relative_path.starts_with("../") {
+                    relative_path.to_owned()
+                } else {
+                    format!("./{relative_path}")
+                }
+            }
+            SpecifierStyle::Absolute => absolute_path.to_owned(),
+            SpecifierStyle::FileUrl => format!("file://{absolute_path}"),
+            SpecifierStyle::ImportMap => format!(
+                "{}{}",
+                config.import_map_prefix,
+                path.strip_prefix(&self.cwd).ok()?.to_str()?
+            ),
+            SpecifierStyle::RootRelative => path.strip_prefix(&self.cwd).ok()?.to_str()?.to_owned(),
+        };
+        let imported_path = match Path::new(&imported_path)
+            .extension()
+            .and_then(OsStr::to_str)
+            .and_then(|extension| config.module_system.get_artifact_extension(extension))
+        {
+            Some(extension) => Path::new(&imported_path)
+                .with_extension(extension)
+                .to_str()?
+                .to_owned(),
+            None => imported_path,
         };
+        let imported_path = if config.collapse_index
+            && config.target != Target::Deno
+            && config.specifier_style != SpecifierStyle::FileUrl
+        {
+            collapse_index_path(imported_path)
+        } else {
+            imported_path
+        };
+
+        // File systems like APFS give back names in NFD, so paths are normalized to NFC to emit the same output
+        // everywhere. `file_path` is kept as-is, since it's used to read the file.
         Some(ImportPaths {
-            absolute_path,
-            imported_path,
+            absolute_path: to_nfc(&absolute_path),
+            file_path,
+            imported_path: to_nfc(&imported_path),
+            relative_to_glob: PathBuf::from(to_nfc(&relative_to_glob.to_string_lossy())),
         })
     }
 
-    fn next_id(&self, starting_id: &str) -> String {
+    /// Map a path as seen by the plugin (i.e., prefixed with the `/cwd` mount point) back to its location on the host.
+    fn host_path(&self, path: &Path) -> PathBuf {
+        self.cwd.join(path.strip_prefix("/cwd").unwrap_or(path))
+    }
+
+    /// Record that the file needs to import `imported` from `source` as `local`, which is added to the top of the file
+    /// once, however many imports need it.
+    fn require_named_import(&self, imported: &str, local: &str, source: &str) {
+        let mut named_imports = self.named_imports.borrow_mut();
+        if !named_imports
+            .iter()
+            .any(|(_, existing, _)| existing == local)
+        {
+            named_imports.push((imported.to_owned(), local.to_owned(), source.to_owned()));
+        }
+    }
+
+    /// Get the path of the file being transformed, relative to the project root when possible.
+    fn importer_path(&self) -> String {
+        self.filename
+            .strip_prefix(&self.cwd)
+            .unwrap_or(&self.filename)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Check whether the file opts out of the plugin with an `/* import-glob-array-disable */` comment at its top.
+    fn is_disabled(&self, module: &Module) -> bool {
+        let Some(comments) = &self.comments else {
+            return false;
+        };
+
+        [
+            Some(module.span.lo),
+            module.body.first().map(|item| item.span_lo()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|pos| comments.get_leading(pos))
+        .flatten()
+        .any(|comment| comment.text.trim() == DISABLE_DIRECTIVE)
+    }
+
+    /// Check whether the file being transformed matches the configured `include` patterns (if there are any), and none
+    /// of the `exclude` patterns, relative to the project root.
+    fn is_included(&self) -> bool {
+        let importer_path = self.importer_path();
+        let matches_any = |patterns: &[String]| {
+            patterns.iter().any(|pattern| {
+                Pattern::new(pattern).map_or(false, |pattern| pattern.matches(&importer_path))
+            })
+        };
+
+        (self.config.include.is_empty() || matches_any(&self.config.include))
+            && !matches_any(&self.config.exclude)
+    }
+
+    /// Check whether a binding is a part of a merged binding, which is used even when the module doesn't reference it.
+    fn is_merged_part(&self, name: &str) -> bool {
+        self.merged_bindings
+            .borrow()
+            .iter()
+            .any(|merged| merged.parts.iter().any(|part| part == name))
+    }
+
+    /// Check whether SWC is building for production, according to its environment (`envName`, or `NODE_ENV`).
+    fn is_production(&self) -> bool {
+        self.env.as_deref() == Some("production")
+    }
+
+    /// Check whether the file being transformed is a TypeScript file, whose generated bindings can be annotated.
+    fn is_typescript(&self) -> bool {
+        matches!(
+            self.filename.extension().and_then(OsStr::to_str),
+            Some("ts" | "tsx" | "mts" | "cts")
+        )
+    }
+
+    /// Get the root of the project, as seen by the plugin.
+    fn project_root(&self) -> PathBuf {
+        if self.importer_dir().starts_with("/cwd") {
+            PathBuf::from("/cwd")
+        } else {
+            self.cwd.to_owned()
+        }
+    }
+
+    /// Get the directory of the file being transformed, as seen by the plugin.
+    fn importer_dir(&self) -> PathBuf {
+        let filename = PathBuf::from("/cwd").join(&self.filename);
+        filename.parent().map(Path::to_path_buf).unwrap_or(filename)
+    }
+
+    /// Get the next placeholder name, e.g., `_iga1`. With [Compat::Babel](Compat::Babel), names follow Babel's
+    /// `generateUidIdentifier` instead, where the first name has no suffix and the next one starts at `2`.
+    fn next_id(&self, config: &Config, starting_id: &str) -> String {
         *self.id_counter.borrow_mut() = self.id_counter.take() + 1;
-        format!("{}{}", starting_id, self.id_counter.borrow())
+        let id = *self.id_counter.borrow();
+
+        let name = match (config.compat, id) {
+            (Compat::Babel, 1) => starting_id.to_owned(),
+            _ => format!("{starting_id}{id}"),
+        };
+        self.generated_names.borrow_mut().insert(name.to_owned());
+        name
     }
 
-    fn new(cwd: PathBuf, filename: PathBuf) -> impl Fold {
+    /// Record the matches of an expanded `pattern` for the manifest, the stamp file, and the editor index, relative to
+    /// the project root.
+    fn record_manifest_entry(&self, pattern: &str, matches: &[ImportPaths]) {
+        if self.config.manifest.is_none()
+            && self.config.stamp_file.is_none()
+            && !self.config.editor_index
+        {
+            return;
+        }
+
+        let matches: Vec<Value> = matches
+            .iter()
+            .map(|import_paths| {
+                let path = Path::new(&import_paths.absolute_path);
+                Value::from(
+                    path.strip_prefix(&self.cwd)
+                        .unwrap_or(path)
+                        .to_string_lossy(),
+                )
+            })
+            .collect();
+
+        if self.config.editor_index {
+            self.unindexed_matches
+                .borrow_mut()
+                .push((pattern.to_owned(), matches.to_owned()));
+        }
+        if self.config.manifest.is_some() || self.config.stamp_file.is_some() {
+            self.manifest_patterns
+                .borrow_mut()
+                .insert(pattern.to_owned(), matches.into());
+        }
+    }
+
+    /// Add the matches that were recorded since the last expansion to the editor index, along with the location of the
+    /// import (or call) at `span` that expanded them.
+    fn index_expansion(&self, span: Span) {
+        let unindexed_matches = self.unindexed_matches.take();
+        if unindexed_matches.is_empty() {
+            return;
+        }
+
+        let location = self.get_location(span);
+        self.index_entries
+            .borrow_mut()
+            .extend(unindexed_matches.into_iter().map(|(pattern, matches)| {
+                json!({ "matches": matches, "pattern": pattern, "span": location })
+            }));
+    }
+
+    /// Record the directories that a glob looks into for the watch manifest, relative to the project root.
+    fn record_watched_dirs(&self, glob_path: &Path, base: &Path) {
+        if self.config.watch_manifest.is_none() {
+            return;
+        }
+
+        self.watched_dirs
+            .borrow_mut()
+            .extend(get_globbed_dirs(glob_path, base).iter().map(|dir| {
+                let dir = self.host_path(dir);
+                dir.strip_prefix(&self.cwd)
+                    .unwrap_or(&dir)
+                    .to_string_lossy()
+                    .into_owned()
+            }));
+    }
+
+    /// Report how many globs the file expanded and how many files they matched, according to `match_report`. Files
+    /// without any globs aren't reported.
+    fn report_match_counts(&self, module: &mut Module) {
+        let match_counts = self.match_counts.take();
+        if match_counts.is_empty() {
+            return;
+        }
+
+        let globs = match_counts.len();
+        let files: usize = match_counts.iter().sum();
+        let summary = format!(
+            "{globs} {} matched {files} {}",
+            if globs == 1 { "glob" } else { "globs" },
+            if files == 1 { "file" } else { "files" }
+        );
+
+        match self.config.match_report {
+            MatchReport::None => {}
+            MatchReport::Comment => {
+                if let Some(first) = module.body.first_mut() {
+                    add_leading_comment(
+                        &self.comments,
+                        first,
+                        CommentKind::Block,
+                        format!(" import-glob-array: {summary} "),
+                    );
+                }
+            }
+            MatchReport::Diagnostic => HANDLER.with(|handler| {
+                handler.note_without_error(&format!("{}: {summary}", self.importer_path()))
+            }),
+        }
+    }
+
+    /// Record the fingerprint of each pattern's matches in the stamp file at `stamp_path`, and report a note for each
+    /// pattern that matches a different set of files than it did in the previous build.
+    fn report_changed_patterns(&self, stamp_path: &Path, patterns: &Map<String, Value>) {
+        let changed = match update_stamp_entry(stamp_path, &self.importer_path(), patterns) {
+            Ok(changed) => changed,
+            Err(err) => {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the stamp file: {err}"
+                    ))
+                });
+                return;
+            }
+        };
+
+        changed.into_iter().for_each(|changed| {
+            HANDLER.with(|handler| {
+                handler.note_without_error(&format!(
+                    "{}: the matches of `{}` changed since the previous build, from {} to {} {}",
+                    self.importer_path(),
+                    changed.pattern,
+                    changed.previous_count,
+                    changed.count,
+                    if changed.count == 1 { "file" } else { "files" }
+                ))
+            })
+        });
+    }
+
+    /// Summarize the errors that were reported while transforming the file, i.e., since there were `err_count` errors.
+    /// Every glob is expanded even when an earlier one fails, so that all of a file's problems show up in one build.
+    fn report_err_count(&self, err_count: usize) {
+        let errs = HANDLER.with(|handler| handler.err_count()) - err_count;
+        if errs == 0 {
+            return;
+        }
+
+        HANDLER.with(|handler| {
+            handler.note_without_error(&format!(
+                "Import Glob Array Plugin found {errs} {} in {}",
+                if errs == 1 { "problem" } else { "problems" },
+                self.importer_path()
+            ))
+        });
+    }
+
+    /// Resolve a pattern that starts with one of the configured `paths` aliases to a pattern, relative to the file
+    /// being transformed, for each of the alias's targets. When several aliases match, the one with the longest prefix
+    /// is used, like `tsc` does.
+    fn resolve_path_alias(&self, config: &Config, pattern: &str) -> Option<Vec<String>> {
+        let (captured, targets) = config
+            .paths
+            .iter()
+            .filter_map(|(alias, targets)| {
+                let (prefix, suffix) = alias.split_once('*').unwrap_or((alias.as_str(), ""));
+                let captured = pattern
+                    .strip_prefix(prefix)?
+                    .strip_suffix(suffix)
+                    .filter(|_| alias.contains('*') || pattern == alias)?;
+                Some((prefix.len(), captured, targets))
+            })
+            .max_by_key(|(prefix_len, _, _)| *prefix_len)
+            .map(|(_, captured, targets)| (captured, targets))?;
+
+        Some(
+            targets
+                .iter()
+                .map(|target| {
+                    self.get_relative_pattern(
+                        &self.project_root().join(target.replacen('*', captured, 1)),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Resolve a relative pattern against every configured root, when the file being transformed is in one of them,
+    /// e.g., `./handlers/*.js` in `src/app/index.js` becomes `./handlers/*.js` and `../../lib/app/handlers/*.js` for
+    /// the `src` and `lib` roots. Patterns of files outside of every root give back `None`.
+    fn resolve_root_patterns(&self, config: &Config, pattern: &str) -> Option<Vec<String>> {
+        if !pattern.starts_with('.') {
+            return None;
+        }
+
+        let project_root = self.project_root();
+        let importer_dir = self.importer_dir();
+        let within_root = config.roots.iter().find_map(|root| {
+            importer_dir
+                .strip_prefix(get_normalized_path(&project_root.join(root)))
+                .ok()
+        })?;
+
+        Some(
+            config
+                .roots
+                .iter()
+                .map(|root| {
+                    self.get_relative_pattern(
+                        &project_root.join(root).join(within_root).join(pattern),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Resolve a bare specifier pattern (e.g., `date-fns/locale/*/index.js`) to a pattern, relative to the file being
+    /// transformed, over the project's `node_modules` directory.
+    fn resolve_package_pattern(&self, pattern: &str) -> String {
+        self.get_relative_pattern(&self.node_modules().join(pattern))
+    }
+
+    /// Get the `node_modules` directory of the project, as seen by the plugin.
+    fn node_modules(&self) -> PathBuf {
+        self.project_root().join("node_modules")
+    }
+
+    /// Get a pattern that points to `target` (as seen by the plugin) relative to the file being transformed.
+    fn get_relative_pattern(&self, target: &Path) -> String {
+        let relative_path = get_relative_path(&self.importer_dir(), &get_normalized_path(target));
+        let relative_path = relative_path.to_string_lossy();
+
+        if relative_path.starts_with("../") {
+            relative_path.into_owned()
+        } else {
+            format!("./{relative_path}")
+        }
+    }
+
+    /// Split the specifiers of a glob import into the ones that an earlier import of the same pattern (with the same
+    /// config) already generated a binding for, which become aliases of that binding, and the ones that still need to
+    /// be expanded, which get recorded for later imports to share.
+    fn share_expanded_bindings(
+        &self,
+        config: &Config,
+        pattern: &str,
+        specifiers: Vec<SWCImportSpecifier>,
+    ) -> (Vec<ModuleItem>, Vec<SWCImportSpecifier>) {
+        let config_key = serde_json::to_string(config).unwrap_or_default();
+        let mut expanded_bindings = self.expanded_bindings.borrow_mut();
+        let mut aliases: Vec<ModuleItem> = vec![];
+        let mut remaining: Vec<SWCImportSpecifier> = vec![];
+
+        specifiers
+            .into_iter()
+            .map(ImportSpecifier::from)
+            .for_each(|specifier| {
+                let key = format!("{config_key}\n{pattern}\n{}", specifier.get_binding_key());
+
+                match expanded_bindings.get(&key) {
+                    Some(binding) => {
+                        aliases.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                            kind: config.declaration_kind.into(),
+                            ..get_var_decl(specifier.get_local_pat(), get_ident_expr(binding))
+                        })))))
+                    }
+                    None => {
+                        expanded_bindings.insert(key, specifier.get_local_name());
+                        remaining.push(specifier.into_inner());
+                    }
+                }
+            });
+        (aliases, remaining)
+    }
+
+    /// Record the default and namespace specifiers of the glob import at `index` (among the module's items) whose matches
+    /// are merged with those of other imports, i.e., the ones whose local name another glob import also binds, or all of
+    /// them when `merge_into` is set. Those that would bind the merged binding itself are renamed to a placeholder, so
+    /// each import declares its own part of it. Imports that disagree on `flatten`, or on the declaration kind of the
+    /// merged binding, are reported, and the merged binding keeps those of the first import.
+    fn merge_specifiers(
+        &self,
+        config: &Config,
+        index: usize,
+        specifiers: Vec<SWCImportSpecifier>,
+    ) -> Vec<SWCImportSpecifier> {
+        specifiers
+            .into_iter()
+            .map(|mut specifier| {
+                let local = match &mut specifier {
+                    SWCImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                    | SWCImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => local,
+                    SWCImportSpecifier::Named(_) => return specifier,
+                };
+                let is_duplicate = self.duplicate_locals.contains(&*local.sym);
+                let Some(name) = config
+                    .merge_into
+                    .to_owned()
+                    .or_else(|| is_duplicate.then(|| local.sym.to_string()))
+                else {
+                    return specifier;
+                };
+
+                if !config.has_array_bindings() {
+                    report_error(
+                        local.span,
+                        &format!(
+                            "`{name}` is bound by several glob imports, but their matches can only be merged when \
+                             the bindings are arrays"
+                        ),
+                    );
+                    return specifier;
+                }
+                if is_duplicate || *local.sym == *name {
+                    local.sym = self.next_id(config, "_iga").into();
+                }
+
+                let kind = VarDeclKind::from(config.declaration_kind);
+                let part = local.sym.to_string();
+                let mut merged_bindings = self.merged_bindings.borrow_mut();
+                match merged_bindings.iter_mut().find(|merged| merged.name == name) {
+                    Some(merged) => {
+                        if merged.flatten != config.flatten {
+                            report_error(
+                                local.span,
+                                &format!(
+                                    "`{name}` is merged from glob imports that disagree on whether to flatten \
+                                     their matches"
+                                ),
+                            );
+                        }
+                        if merged.kind != kind {
+                            report_error(
+                                local.span,
+                                &format!(
+                                    "`{name}` is merged from glob imports that disagree on its declaration kind"
+                                ),
+                            );
+                        }
+                        merged.last_index = index;
+                        merged.parts.push(part);
+                    }
+                    None => merged_bindings.push(MergedBinding {
+                        flatten: config.flatten,
+                        kind,
+                        last_index: index,
+                        name,
+                        parts: vec![part],
+                    }),
+                }
+                specifier
+            })
+            .collect()
+    }
+
+    /// Get the location of the configured glob cache, as seen by the plugin.
+    fn cache_path(&self) -> Option<PathBuf> {
+        let cache = self.config.cache.as_ref()?;
+        Some(get_normalized_path(&self.project_root().join(cache)))
+    }
+
+    /// Get the location of the configured manifest, as seen by the plugin.
+    fn manifest_path(&self) -> Option<PathBuf> {
+        let manifest = self.config.manifest.as_ref()?;
+        Some(get_normalized_path(&self.project_root().join(manifest)))
+    }
+
+    /// Get the location of the configured stamp file, as seen by the plugin.
+    fn stamp_path(&self) -> Option<PathBuf> {
+        let stamp_file = self.config.stamp_file.as_ref()?;
+        Some(get_normalized_path(&self.project_root().join(stamp_file)))
+    }
+
+    /// Get the location of the configured watch manifest, as seen by the plugin.
+    fn watch_manifest_path(&self) -> Option<PathBuf> {
+        let watch_manifest = self.config.watch_manifest.as_ref()?;
+        Some(get_normalized_path(
+            &self.project_root().join(watch_manifest),
+        ))
+    }
+
+    fn new(
+        cwd: PathBuf,
+        filename: PathBuf,
+        env: Option<String>,
+        config: Config,
+        comments: Option<C>,
+        source_map: Option<Lrc<SourceMapperDyn>>,
+    ) -> Self {
         Self {
+            comments,
+            config,
             cwd,
+            duplicate_locals: HashSet::new(),
+            env,
+            expanded_bindings: Rc::new(RefCell::new(HashMap::new())),
             filename,
+            generated_names: Rc::new(RefCell::new(HashSet::new())),
+            glob_cache: Rc::new(RefCell::new(GlobCache::default())),
             id_counter: Rc::new(RefCell::new(0)),
+            index_entries: Rc::new(RefCell::new(vec![])),
+            manifest_patterns: Rc::new(RefCell::new(Map::new())),
+            match_counts: Rc::new(RefCell::new(vec![])),
+            merged_bindings: Rc::new(RefCell::new(vec![])),
+            named_imports: Rc::new(RefCell::new(vec![])),
+            needs_create_require: Rc::new(RefCell::new(false)),
+            referenced_names: HashSet::new(),
+            source_map: source_map.map(SourceMap),
+            unindexed_matches: Rc::new(RefCell::new(vec![])),
+            watched_dirs: Rc::new(RefCell::new(BTreeSet::new())),
         }
     }
 }
 
-impl Fold for ImportGlobArrayPlugin {
+impl<C: Comments> Fold for ImportGlobArrayPlugin<C> {
+    #[instrument(
+        level = "info",
+        name = "import_glob_array",
+        skip_all,
+        fields(filename = %self.filename.display())
+    )]
     fn fold_module(&mut self, mut module: Module) -> Module {
-        module.body = module
+        if self.is_disabled(&module) || !self.is_included() {
+            return module;
+        }
+
+        let started = Instant::now();
+        let err_count = HANDLER.with(|handler| handler.err_count());
+
+        match self.get_file_config() {
+            Ok(config) => self.config = config,
+            Err(err) => HANDLER.with(|handler| {
+                handler.err(&format!(
+                    "Import Glob Array Plugin received an invalid {RC_FILE_NAME}: {err}"
+                ))
+            }),
+        }
+
+        if self.config.analyze_only {
+            let imports = collect_glob_imports(self, &module);
+            let analysis_path =
+                get_normalized_path(&self.project_root().join(&self.config.analysis_file));
+            if let Err(err) =
+                write_analysis_entry(&analysis_path, &self.importer_path(), &imports, |span| {
+                    self.get_location(span)
+                })
+            {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the analysis file: {err}"
+                    ))
+                });
+            }
+            self.report_err_count(err_count);
+            return module;
+        }
+
+        self.referenced_names = get_referenced_names(&module);
+        self.duplicate_locals = self.get_duplicate_locals(&module);
+        let node_count = self.config.emit_stats.then(|| count_nodes(&module));
+
+        let mut item_groups: Vec<Vec<ModuleItem>> = module
             .body
             .into_iter()
-            .flat_map(|item| match item {
-                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                    src, specifiers, ..
-                })) if (src.value.starts_with('.') || src.value.starts_with('/'))
-                    && is_glob(&src.value.to_string()) =>
-                {
-                    self.build_module_items(transform_import_decl(&self, src, specifiers))
+            .enumerate()
+            .map(|(index, item)| {
+                let pattern = self.get_item_glob_pattern(&item);
+                let span = item.span();
+                let is_glob = pattern.is_some();
+                let items = match (item, pattern) {
+                    (ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)), Some(pattern)) => {
+                        let config = self.get_overridden_config(
+                            import_decl.span,
+                            get_source_overrides(
+                                &import_decl.src.value,
+                                import_decl.asserts.as_deref(),
+                            ),
+                        );
+                        let original = config
+                            .preserve_original_import_as_comment
+                            .then(|| get_import_source(&import_decl));
+                        let specifiers =
+                            self.merge_specifiers(&config, index, import_decl.specifiers);
+                        let mut items = self.expand_import(config, &pattern, specifiers);
+
+                        if let (Some(original), Some(first)) = (original, items.first_mut()) {
+                            add_leading_comment(
+                                &self.comments,
+                                first,
+                                CommentKind::Line,
+                                format!(" {original}"),
+                            );
+                        }
+                        items
+                    }
+                    (
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export)),
+                        Some(pattern),
+                    ) => self.expand_reexport(named_export, &pattern),
+                    (
+                        ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals)),
+                        Some(pattern),
+                    ) => self.expand_import_equals(*import_equals, &pattern),
+                    (ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)), Some(pattern)) => {
+                        self.expand_export_all(export_all, &pattern)
+                    }
+                    (ModuleItem::ModuleDecl(ModuleDecl::Import(mut import_decl)), None)
+                        if &*import_decl.src.value == MANIFEST_SPECIFIER =>
+                    {
+                        if let Some(manifest_path) = self.manifest_path() {
+                            let specifier = get_relative_path(&self.importer_dir(), &manifest_path);
+                            let specifier = specifier.to_string_lossy();
+                            let specifier = if specifier.starts_with("../") {
+                                specifier.into_owned()
+                            } else {
+                                format!("./{specifier}")
+                            };
+
+                            import_decl.src = Box::new(Str {
+                                raw: None,
+                                span: import_decl.src.span,
+                                value: specifier.into(),
+                            });
+                        }
+                        vec![ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))]
+                    }
+                    (item, _) => vec![item],
+                };
+
+                if is_glob {
+                    self.index_expansion(span);
                 }
-                _ => vec![item],
+                items
             })
             .collect();
+
+        self.merged_bindings.take().into_iter().for_each(|merged| {
+            item_groups[merged.last_index].push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(
+                VarDecl {
+                    kind: merged.kind,
+                    ..get_var_decl(
+                        get_ident_pat(&merged.name),
+                        get_ident_array_expr(&merged.parts, merged.flatten),
+                    )
+                },
+            )))));
+        });
+        module.body = item_groups.into_iter().flatten().collect();
+
+        module.body = module.body.fold_with(self);
+
+        if *self.needs_create_require.borrow() {
+            module.body.splice(0..0, get_create_require_items());
+        }
+
+        let named_imports = self.named_imports.take();
+        module.body.splice(
+            0..0,
+            named_imports.iter().map(|(imported, local, source)| {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(get_named_import_decl(
+                    imported, local, source,
+                )))
+            }),
+        );
+
+        let match_counts = self.match_counts.borrow().to_owned();
+        self.report_match_counts(&mut module);
+
+        let patterns = self.manifest_patterns.take();
+        if let Some(stamp_path) = self.stamp_path() {
+            self.report_changed_patterns(&stamp_path, &patterns);
+        }
+
+        if let Some(manifest_path) = self.manifest_path() {
+            if let Err(err) = write_manifest_entry(&manifest_path, &self.importer_path(), patterns)
+            {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the manifest: {err}"
+                    ))
+                });
+            }
+        }
+
+        if self.config.editor_index {
+            let index_path = self.project_root().join(EDITOR_INDEX_FILE_NAME);
+            let entries = self.index_entries.take();
+            if let Err(err) = write_editor_index_entry(&index_path, &self.importer_path(), entries)
+            {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the editor index: {err}"
+                    ))
+                });
+            }
+        }
+
+        if let Some(cache_path) = self.cache_path() {
+            if let Err(err) = self.glob_cache.borrow_mut().write(&cache_path) {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the glob cache: {err}"
+                    ))
+                });
+            }
+        }
+
+        if let Some(watch_manifest_path) = self.watch_manifest_path() {
+            let dirs = self.watched_dirs.take().into_iter().collect();
+            if let Err(err) =
+                write_watch_manifest_entry(&watch_manifest_path, &self.importer_path(), dirs)
+            {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the watch manifest: {err}"
+                    ))
+                });
+            }
+        }
+
+        mark_generated_idents(&mut module, &self.generated_names.take());
+
+        if let Some(node_count) = node_count {
+            let stats = FileStats {
+                duration: started.elapsed(),
+                generated_nodes: count_nodes(&module).saturating_sub(node_count),
+                globs: match_counts.len(),
+                matches: match_counts.iter().sum(),
+            };
+            let stats_path =
+                get_normalized_path(&self.project_root().join(&self.config.stats_file));
+            if let Err(err) = write_stats_entry(&stats_path, &self.importer_path(), stats) {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Import Glob Array Plugin couldn't update the stats file: {err}"
+                    ))
+                });
+            }
+        }
+
+        self.report_err_count(err_count);
         module
     }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children_with(self);
+        let Expr::Call(call) = expr else {
+            return expr;
+        };
+
+        match self.get_resolve_call_pattern(&call) {
+            Some(pattern) => {
+                let span = call.span;
+                let expr = self.expand_resolve_call(call, &pattern);
+                self.index_expansion(span);
+                expr
+            }
+            None => Expr::Call(call),
+        }
+    }
+}
+
+fn report_error(span: Span, message: &str) {
+    HANDLER.with(|handler| handler.struct_span_err(span, message).emit());
 }
 
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let config = Config::parse(
+        &metadata
+            .get_transform_plugin_config()
+            .unwrap_or_else(|| "{}".into()),
+    )
+    .unwrap_or_else(|err| panic!("Import Glob Array Plugin received an invalid config: {err}"));
     let cwd = metadata
         .get_context(&Cwd)
         .map(PathBuf::from)
@@ -117,31 +1582,404 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
         .get_context(&Filename)
         .map(PathBuf::from)
         .expect("Import Glob Array Plugin requires filename metadata");
-    let mut plugin = ImportGlobArrayPlugin::new(cwd, filename);
+    let env = metadata.get_context(&Env);
+    let mut plugin = ImportGlobArrayPlugin::new(
+        cwd,
+        filename,
+        env,
+        config,
+        metadata.comments,
+        Some(Lrc::new(metadata.source_map)),
+    );
     program.fold_with(&mut plugin)
 }
 
+/// Collect every glob import of `program`, along with the files it matches, without transforming the program, for
+/// linters, CI checks, and migration tooling that build on the plugin. `config` is the plugin's config, as it would be
+/// passed in `.swcrc`, and `comments` are the program's comments, which directives are read from. Problems with the
+/// imports are reported through SWC's `HANDLER`, like they are by the plugin, so one has to be set.
+pub fn analyze<C: Comments>(
+    program: &Program,
+    cwd: PathBuf,
+    filename: PathBuf,
+    config: &str,
+    comments: Option<C>,
+) -> Result<Vec<GlobImport>, String> {
+    let Program::Module(module) = program else {
+        return Ok(vec![]);
+    };
+    let config = Config::parse(config).map_err(|err| format!("invalid config: {err}"))?;
+    let mut plugin = ImportGlobArrayPlugin::new(cwd, filename, None, config, comments, None);
+    if plugin.is_disabled(module) || !plugin.is_included() {
+        return Ok(vec![]);
+    }
+
+    plugin.config = plugin
+        .get_file_config()
+        .map_err(|err| format!("invalid {RC_FILE_NAME}: {err}"))?;
+    Ok(collect_glob_imports(&plugin, module))
+}
+
+/// Get the [JSON Schema](https://json-schema.org/) of the plugin's options, pretty-printed, which the `config-schema`
+/// binary writes to `config.schema.json`.
+#[cfg(feature = "schema")]
+pub fn config_schema() -> String {
+    serde_json::to_string_pretty(&schemars::schema_for!(Config)).unwrap() + "\n"
+}
+
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::env::consts::OS;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
+    use proptest::prelude::*;
+    use serde_json::{json, Value};
+    use swc_core::common::comments::SingleThreadedComments;
+    use swc_core::common::sync::Lrc;
+    use swc_core::common::{Globals, SourceMap, Span, Spanned, DUMMY_SP, GLOBALS};
+    use swc_core::ecma::ast::{
+        Ident, ImportDefaultSpecifier, ImportSpecifier as SWCImportSpecifier, Module, ModuleDecl,
+        ModuleItem, Program,
+    };
+    use swc_core::ecma::parser::{EsConfig, Syntax};
     use swc_core::ecma::transforms::testing::{test_fixture, FixtureTestConfig};
+    use swc_core::ecma::visit::FoldWith;
     use swc_core::testing::fixture;
 
-    use crate::ImportGlobArrayPlugin;
+    use crate::config::{Config, DeclarationKind, Sort};
+    use crate::manifest::EDITOR_INDEX_FILE_NAME;
+    use crate::test_utils::{capture_diagnostics, parse_module, temp_dir};
+    use crate::utils::get_normalized_path;
+    use crate::{analyze, ImportGlobArrayPlugin, RC_FILE_NAME};
+
+    /// A path segment, such as a directory or file name.
+    fn segment() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9_-]{0,7}"
+    }
+
+    /// A relative path of up to `max_segments` segments.
+    fn relative_path(max_segments: usize) -> impl Strategy<Value = PathBuf> {
+        prop::collection::vec(segment(), 0..=max_segments)
+            .prop_map(|segments| segments.iter().collect())
+    }
+
+    proptest! {
+        /// Whatever the project, importer, and match, `get_paths` gives back a specifier that is relative to the
+        /// importer and resolves back to the match, and the match's path relative to the glob's base directory.
+        #[test]
+        fn get_paths_round_trips(
+            cwd in relative_path(3),
+            importer_dir in relative_path(3),
+            glob_base in relative_path(2),
+            within_base in relative_path(2),
+            name in segment(),
+        ) {
+            let cwd = Path::new("/").join(cwd);
+            let filename = importer_dir.join("index.js");
+            let glob_base = Path::new("/cwd").join(glob_base);
+            let path = glob_base.join(&within_base).join(format!("{name}.js"));
+
+            let plugin = ImportGlobArrayPlugin::<SingleThreadedComments>::new(
+                cwd.clone(),
+                filename,
+                None,
+                Config::default(),
+                None,
+                None,
+            );
+            let import_paths = plugin.get_paths(&plugin.config, &path, &glob_base).unwrap();
 
-    #[fixture("tests/fixtures/**/input.js")]
+            prop_assert!(
+                import_paths.imported_path.starts_with("./")
+                    || import_paths.imported_path.starts_with("../")
+            );
+            prop_assert_eq!(
+                get_normalized_path(&plugin.importer_dir().join(&import_paths.imported_path)),
+                path.clone()
+            );
+            prop_assert_eq!(
+                PathBuf::from(&import_paths.absolute_path),
+                plugin.host_path(&path)
+            );
+            prop_assert_eq!(
+                import_paths.relative_to_glob,
+                within_base.join(format!("{name}.js"))
+            );
+        }
+    }
+
+    /// Imports that are merged into the same binding can't disagree on how it's declared.
+    #[test]
+    fn merged_imports_must_agree() {
+        let plugin = ImportGlobArrayPlugin::<SingleThreadedComments>::new(
+            PathBuf::from("/"),
+            PathBuf::from("index.js"),
+            None,
+            Config::default(),
+            None,
+            None,
+        );
+        let merge_into = |local: &str, config: Config| {
+            let specifier = SWCImportSpecifier::Default(ImportDefaultSpecifier {
+                local: Ident::new(local.into(), DUMMY_SP),
+                span: DUMMY_SP,
+            });
+            let config = Config {
+                merge_into: Some("commands".into()),
+                ..config
+            };
+            plugin.merge_specifiers(&config, 0, vec![specifier]);
+        };
+
+        let ((), messages) = capture_diagnostics(|| {
+            merge_into("core", Config::default());
+            merge_into(
+                "plugins",
+                Config {
+                    declaration_kind: DeclarationKind::Let,
+                    flatten: false,
+                    ..Config::default()
+                },
+            );
+        });
+
+        assert_eq!(
+            messages,
+            [
+                "`commands` is merged from glob imports that disagree on whether to flatten their matches",
+                "`commands` is merged from glob imports that disagree on its declaration kind",
+            ]
+        );
+    }
+
+    #[test]
+    fn rc_file_preset_keeps_options_of_the_plugin_config() {
+        let cwd = temp_dir("rc-file-preset");
+        fs::write(cwd.join(RC_FILE_NAME), r#"{ "preset": "content-site" }"#).unwrap();
+        let plugin = ImportGlobArrayPlugin::<SingleThreadedComments>::new(
+            cwd.clone(),
+            cwd.join("index.js"),
+            None,
+            Config::parse(r#"{ "sort": "mtime" }"#).unwrap(),
+            None,
+            None,
+        );
+
+        let config = plugin.get_file_config().unwrap();
+        assert_eq!(config.sort, Sort::Mtime);
+        assert!(config.reading_time);
+    }
+
+    /// Transform `src` as the file `index.js` of the project at `cwd`, and give back the transformed module, along
+    /// with the message of each diagnostic that was reported.
+    fn transform(cwd: &Path, config: Config, src: &str) -> (Module, Vec<String>) {
+        let cm = Lrc::new(SourceMap::default());
+        let module = parse_module(&cm, src);
+
+        capture_diagnostics(|| {
+            GLOBALS.set(&Globals::new(), || {
+                let mut plugin = ImportGlobArrayPlugin::<SingleThreadedComments>::new(
+                    cwd.to_path_buf(),
+                    cwd.join("index.js"),
+                    None,
+                    config,
+                    None,
+                    Some(cm.clone()),
+                );
+                module.fold_with(&mut plugin)
+            })
+        })
+    }
+
+    #[test]
+    fn editor_index_lists_glob_imports() {
+        let cwd = temp_dir("editor-index");
+        fs::create_dir_all(cwd.join("docs")).unwrap();
+        fs::write(cwd.join("docs/a.md"), "").unwrap();
+        fs::write(cwd.join("docs/b.md"), "").unwrap();
+        let config = Config {
+            editor_index: true,
+            ..Config::default()
+        };
+        let read_index = || -> Value {
+            serde_json::from_str(&fs::read_to_string(cwd.join(EDITOR_INDEX_FILE_NAME)).unwrap())
+                .unwrap()
+        };
+
+        let (_, messages) = transform(&cwd, config.clone(), "import docs from \"./docs/*.md\";");
+        assert!(messages.is_empty());
+        assert_eq!(
+            read_index(),
+            json!({
+                "index.js": [
+                    {
+                        "matches": ["docs/a.md", "docs/b.md"],
+                        "pattern": "./docs/*.md",
+                        "span": {
+                            "start": { "line": 1, "column": 0 },
+                            "end": { "line": 1, "column": 31 },
+                        },
+                    }
+                ]
+            })
+        );
+
+        let (_, messages) = transform(&cwd, config, "import docs from \"./docs/a.md\";");
+        assert!(messages.is_empty());
+        assert_eq!(read_index(), json!({}));
+    }
+
+    #[test]
+    fn analyze_only_writes_analysis_entry() {
+        let cwd = temp_dir("analyze-only");
+        fs::create_dir_all(cwd.join("docs")).unwrap();
+        fs::write(cwd.join("docs/a.md"), "").unwrap();
+        let config = Config {
+            analysis_file: "analysis.json".into(),
+            analyze_only: true,
+            ..Config::default()
+        };
+
+        let (module, messages) = transform(&cwd, config, "import docs from \"./docs/*.md\";");
+        assert!(messages.is_empty());
+        assert!(matches!(
+            &module.body[..],
+            [ModuleItem::ModuleDecl(ModuleDecl::Import(import))] if &*import.src.value == "./docs/*.md"
+        ));
+
+        let analysis: Value =
+            serde_json::from_str(&fs::read_to_string(cwd.join("analysis.json")).unwrap()).unwrap();
+        assert_eq!(
+            analysis,
+            json!({
+                "index.js": [
+                    {
+                        "matches": [
+                            {
+                                "absolutePath": cwd.join("docs/a.md").to_str().unwrap(),
+                                "importedPath": "./docs/a.md",
+                            }
+                        ],
+                        "pattern": "./docs/*.md",
+                        "span": {
+                            "start": { "line": 1, "column": 0 },
+                            "end": { "line": 1, "column": 31 },
+                        },
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn analyze_reports_glob_imports() {
+        let cwd = temp_dir("analyze");
+        fs::create_dir_all(cwd.join("docs")).unwrap();
+        fs::create_dir_all(cwd.join("lib")).unwrap();
+        fs::write(cwd.join("docs/a.md"), "").unwrap();
+        fs::write(cwd.join("docs/b.md"), "").unwrap();
+        fs::write(cwd.join("lib/utils.js"), "").unwrap();
+        let cm = SourceMap::default();
+        let module = parse_module(
+            &cm,
+            "import docs from \"./docs/*.md\";\nimport { marked } from \"marked\";\nimport lib from \"./lib/*.js\";",
+        );
+        let spans: Vec<Span> = module.body.iter().map(Spanned::span).collect();
+
+        let (imports, messages) = capture_diagnostics(|| {
+            analyze(
+                &Program::Module(module),
+                cwd.clone(),
+                cwd.join("index.js"),
+                "{}",
+                None::<SingleThreadedComments>,
+            )
+            .unwrap()
+        });
+        assert!(messages.is_empty());
+
+        let imports: Vec<(&str, Span, Vec<(String, &str)>)> = imports
+            .iter()
+            .map(|import| {
+                let matches = import
+                    .matches
+                    .iter()
+                    .map(|glob_match| {
+                        (
+                            glob_match.absolute_path.to_owned(),
+                            &*glob_match.imported_path,
+                        )
+                    })
+                    .collect();
+                (&*import.pattern, import.span, matches)
+            })
+            .collect();
+        let absolute_path = |path: &str| cwd.join(path).to_string_lossy().into_owned();
+        assert_eq!(
+            imports,
+            [
+                (
+                    "./docs/*.md",
+                    spans[0],
+                    vec![
+                        (absolute_path("docs/a.md"), "./docs/a.md"),
+                        (absolute_path("docs/b.md"), "./docs/b.md"),
+                    ],
+                ),
+                (
+                    "./lib/*.js",
+                    spans[2],
+                    vec![(absolute_path("lib/utils.js"), "./lib/utils.js")],
+                ),
+            ]
+        );
+    }
+
+    /// Expand each fixture's input and compare it to its output. Fixtures whose output depends on the platform, such
+    /// as path separators on Windows, can provide an alternate output for it, e.g., `output.windows.js`, named after
+    /// [std::env::consts::OS](std::env::consts::OS). Fixtures that depend on the environment, such as `production`, set
+    /// it in an `env` file. Fixtures that report diagnostics compare them to an `output.stderr` file.
+    #[fixture("tests/fixtures/**/input.[jt]s")]
     fn fixture(input: PathBuf) {
         let cwd = input.parent().unwrap().to_path_buf();
-        let output = input.with_file_name("output.js");
+        let extension = input.extension().unwrap().to_str().unwrap();
+        let platform_output = input.with_file_name(format!("output.{OS}.{extension}"));
+        let output = if platform_output.exists() {
+            platform_output
+        } else {
+            input.with_file_name(format!("output.{extension}"))
+        };
+        let syntax = match extension {
+            "ts" => Syntax::Typescript(Default::default()),
+            _ => Syntax::Es(EsConfig {
+                import_assertions: true,
+                ..Default::default()
+            }),
+        };
+        let config: Config = fs::read_to_string(input.with_file_name("config.json"))
+            .map(|config| Config::parse(&config).unwrap())
+            .unwrap_or_default();
+        let env = fs::read_to_string(input.with_file_name("env"))
+            .ok()
+            .map(|env| env.trim().to_owned());
 
         test_fixture(
-            Default::default(),
-            &|_| ImportGlobArrayPlugin::new(cwd.clone(), input.clone()),
+            syntax,
+            &|tester| {
+                ImportGlobArrayPlugin::new(
+                    cwd.clone(),
+                    input.clone(),
+                    env.clone(),
+                    config.clone(),
+                    Some(tester.comments.clone()),
+                    Some(tester.cm.clone()),
+                )
+            },
             &input,
             &output,
             FixtureTestConfig {
-                allow_error: false,
+                allow_error: output.with_extension("stderr").exists(),
                 sourcemap: false,
             },
         )
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+use serde_json::{Map, Value};
+
+use crate::utils::update_json_file;
+
+/// Name of the file at the project root that indexes the glob imports of every file for editor extensions.
+pub(crate) const EDITOR_INDEX_FILE_NAME: &str = ".import-glob-index.json";
+
+/// Specifier that imports the manifest of every expansion in the project.
+pub(crate) const MANIFEST_SPECIFIER: &str = "import-glob-array/manifest";
+
+/// Replace the entry of `importer` in the editor index at `index_path` with its expanded globs, i.e., the pattern,
+/// location, and matches of each. Importers that no longer expand any globs are removed.
+pub(crate) fn write_editor_index_entry(
+    index_path: &Path,
+    importer: &str,
+    entries: Vec<Value>,
+) -> Result<(), String> {
+    let entry = (!entries.is_empty()).then(|| Value::from(entries));
+    write_entry(index_path, importer, entry)
+}
+
+/// Replace the entry of `importer` in the manifest at `manifest_path` with the patterns it expanded, keyed by pattern,
+/// each listing its matches relative to the project root. Importers that no longer expand any patterns are removed.
+pub(crate) fn write_manifest_entry(
+    manifest_path: &Path,
+    importer: &str,
+    patterns: Map<String, Value>,
+) -> Result<(), String> {
+    let entry = (!patterns.is_empty()).then(|| Value::Object(patterns));
+    write_entry(manifest_path, importer, entry)
+}
+
+/// Get every directory that a glob looks into, from its `base` directory down to the directories that its matches are
+/// in, e.g., `routes`, `routes/admin`, and `routes/admin/pages` for `routes/*/pages/*.js`. A `**` covers every directory
+/// below it.
+pub(crate) fn get_globbed_dirs(glob_path: &Path, base: &Path) -> Vec<PathBuf> {
+    let Some(dir_pattern) = glob_path.parent() else {
+        return vec![];
+    };
+
+    dir_pattern
+        .ancestors()
+        .take_while(|ancestor| ancestor.starts_with(base))
+        .filter_map(|ancestor| glob(ancestor.to_str()?).ok())
+        .flat_map(|paths| paths.filter_map(|result| result.ok()))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Replace the entry of `importer` in the watch manifest at `manifest_path` with the directories that its globs looked
+/// into, relative to the project root. Importers that no longer expand any globs are removed.
+pub(crate) fn write_watch_manifest_entry(
+    manifest_path: &Path,
+    importer: &str,
+    dirs: Vec<String>,
+) -> Result<(), String> {
+    let entry = (!dirs.is_empty()).then(|| Value::from(dirs));
+    write_entry(manifest_path, importer, entry)
+}
+
+/// Replace the entry of `importer` in the JSON file at `path`, or remove it when there's no `entry`.
+fn write_entry(path: &Path, importer: &str, entry: Option<Value>) -> Result<(), String> {
+    update_json_file(path, |entries| match entry {
+        Some(entry) => {
+            entries.insert(importer.to_owned(), entry);
+        }
+        None => {
+            entries.remove(importer);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use serde_json::{json, Value};
+
+    use super::{get_globbed_dirs, write_manifest_entry, write_watch_manifest_entry};
+    use crate::test_utils::temp_dir;
+
+    /// Get the globbed directories of `pattern` in `root`, relative to it.
+    fn globbed_dirs(root: &Path, pattern: &str, base: &str) -> BTreeSet<PathBuf> {
+        get_globbed_dirs(&root.join(pattern), &root.join(base))
+            .into_iter()
+            .map(|dir| dir.strip_prefix(root).unwrap().to_path_buf())
+            .collect()
+    }
+
+    fn create_files(root: &Path, paths: &[&str]) {
+        paths.iter().for_each(|path| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        });
+    }
+
+    #[test]
+    fn globbed_dirs_follow_each_wildcard() {
+        let root = temp_dir("globbed-dirs-wildcards");
+        create_files(
+            &root,
+            &[
+                "routes/admin/pages/users.js",
+                "routes/blog/index.js",
+                "other/pages/home.js",
+            ],
+        );
+
+        assert_eq!(
+            globbed_dirs(&root, "routes/*/pages/*.js", "routes"),
+            BTreeSet::from([
+                PathBuf::from("routes"),
+                PathBuf::from("routes/admin"),
+                PathBuf::from("routes/admin/pages"),
+                PathBuf::from("routes/blog"),
+            ])
+        );
+    }
+
+    #[test]
+    fn globbed_dirs_cover_every_dir_below_a_globstar() {
+        let root = temp_dir("globbed-dirs-globstar");
+        create_files(
+            &root,
+            &[
+                "docs/index.md",
+                "docs/guides/setup/linux.md",
+                "docs/api/index.md",
+                "other/index.md",
+            ],
+        );
+
+        assert_eq!(
+            globbed_dirs(&root, "docs/**/*.md", "docs"),
+            BTreeSet::from([
+                PathBuf::from("docs"),
+                PathBuf::from("docs/api"),
+                PathBuf::from("docs/guides"),
+                PathBuf::from("docs/guides/setup"),
+            ])
+        );
+    }
+
+    #[test]
+    fn watch_manifest_entry_is_removed_without_globs() {
+        let manifest_path = temp_dir("watch-manifest").join("watch.json");
+
+        write_watch_manifest_entry(&manifest_path, "src/a.js", vec!["src/pages".into()]).unwrap();
+        write_watch_manifest_entry(&manifest_path, "src/b.js", vec!["src/docs".into()]).unwrap();
+        write_watch_manifest_entry(&manifest_path, "src/a.js", vec![]).unwrap();
+
+        let manifest: Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(manifest, json!({ "src/b.js": ["src/docs"] }));
+    }
+
+    #[test]
+    fn manifest_entry_replaces_a_torn_manifest() {
+        let dir = temp_dir("torn-manifest");
+        let manifest_path = dir.join("manifest.json");
+        fs::write(
+            &manifest_path,
+            r#"{ "src/a.js": { "./pages/*.js": ["src/pa"#,
+        )
+        .unwrap();
+
+        let patterns = json!({ "./docs/*.md": ["src/docs/intro.md"] });
+        write_manifest_entry(
+            &manifest_path,
+            "src/b.js",
+            patterns.as_object().unwrap().clone(),
+        )
+        .unwrap();
+
+        let manifest: Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(manifest, json!({ "src/b.js": patterns }));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+}
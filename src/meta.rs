@@ -0,0 +1,269 @@
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+use swc_core::common::errors::HANDLER;
+use swc_core::ecma::ast::ExprOrSpread;
+
+use crate::config::Config;
+use crate::git::get_git_meta;
+use crate::image::get_image_dimensions;
+use crate::utils::{get_import_map_expr, natural_cmp};
+use crate::ImportPaths;
+
+/// Get the `_importMeta` object of a match, restricted to the configured `meta_fields`. The `dev_only_meta` fields are
+/// left out of production builds, so they don't leak paths or grow bundles there.
+pub(crate) fn get_meta_expr(
+    config: &Config,
+    import_paths: &ImportPaths,
+    project_root: &Path,
+    is_production: bool,
+) -> ExprOrSpread {
+    let excluded: &[String] = if is_production {
+        &config.dev_only_meta
+    } else {
+        &[]
+    };
+    get_import_map_expr(
+        import_paths,
+        &get_match_meta(config, import_paths, project_root),
+        config.meta_fields.as_deref(),
+        excluded,
+    )
+}
+
+/// Get the extra properties of a match's `_importMeta` object, which are the properties computed from its path (such as
+/// `relativeToGlob`) and from the file itself that are turned on, followed by any configured [meta](Config::meta).
+fn get_match_meta(
+    config: &Config,
+    import_paths: &ImportPaths,
+    project_root: &Path,
+) -> Map<String, Value> {
+    let mut meta = Map::new();
+
+    let lists_relative_to_glob = config.meta_fields.as_ref().map_or(false, |fields| {
+        fields.iter().any(|field| field == "relativeToGlob")
+    });
+    if config.relative_to_glob || lists_relative_to_glob {
+        meta.insert(
+            "relativeToGlob".into(),
+            import_paths.relative_to_glob.to_string_lossy().into(),
+        );
+    }
+
+    if config.image_dimensions {
+        if let Some((width, height)) = get_image_dimensions(&import_paths.file_path) {
+            meta.insert("width".into(), width.into());
+            meta.insert("height".into(), height.into());
+        }
+    }
+
+    if config.git_meta {
+        meta.extend(get_git_meta(project_root, &import_paths.file_path));
+    }
+
+    if config.is_symlink {
+        meta.insert("isSymlink".into(), is_linked_match(import_paths).into());
+    }
+
+    if config.reading_time {
+        if let Some(word_count) = get_markdown_word_count(import_paths) {
+            let minutes = (word_count as f64 / config.words_per_minute.max(1) as f64).ceil();
+            meta.insert("wordCount".into(), word_count.into());
+            meta.insert("readingTime".into(), (minutes as u64).into());
+        }
+    }
+
+    meta.extend(config.meta.clone());
+    meta
+}
+
+/// Check whether a match is reached through a symlink below the glob's base directory, i.e., the file itself or one of
+/// the directories between it and the base is a link, such as a linked workspace package.
+fn is_linked_match(import_paths: &ImportPaths) -> bool {
+    import_paths
+        .file_path
+        .ancestors()
+        .take(import_paths.relative_to_glob.components().count())
+        .any(|path| {
+            fs::symlink_metadata(path).map_or(false, |metadata| metadata.file_type().is_symlink())
+        })
+}
+
+/// Count the words of a Markdown (or MDX) match, skipping its front matter, fenced code blocks, and MDX
+/// `import`/`export` statements. Other files give back `None`.
+fn get_markdown_word_count(import_paths: &ImportPaths) -> Option<usize> {
+    let extension = import_paths.file_path.extension().and_then(OsStr::to_str)?;
+    if !matches!(extension, "md" | "mdx") {
+        return None;
+    }
+
+    let contents = fs::read_to_string(&import_paths.file_path).ok()?;
+    let mut lines = contents.lines().peekable();
+
+    if lines.peek().map(|line| line.trim_end()) == Some("---") {
+        lines.next();
+        lines.by_ref().find(|line| line.trim_end() == "---");
+    }
+
+    let mut in_code_block = false;
+    let word_count = lines
+        .filter(|line| {
+            let line = line.trim_start();
+            if line.starts_with("```") || line.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                return false;
+            }
+            !in_code_block
+                && !(extension == "mdx"
+                    && (line.starts_with("import ") || line.starts_with("export ")))
+        })
+        .flat_map(str::split_whitespace)
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count();
+
+    Some(word_count)
+}
+
+/// A front matter value that matches are sorted by. Matches whose value is a number come first, in numeric order,
+/// followed by the other values in natural order, and then by the matches that don't have the field at all.
+#[derive(Debug)]
+pub(crate) enum FrontmatterKey {
+    Number(f64),
+    Text(String),
+    Missing,
+}
+
+impl FrontmatterKey {
+    pub(crate) fn new(import_paths: &ImportPaths, field: &str) -> Self {
+        match get_frontmatter_field(import_paths, field) {
+            Some(value) => match value.parse() {
+                Ok(number) => FrontmatterKey::Number(number),
+                Err(_) => FrontmatterKey::Text(value),
+            },
+            None => FrontmatterKey::Missing,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            FrontmatterKey::Number(_) => 0,
+            FrontmatterKey::Text(_) => 1,
+            FrontmatterKey::Missing => 2,
+        }
+    }
+}
+
+impl Ord for FrontmatterKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (FrontmatterKey::Number(a), FrontmatterKey::Number(b)) => a.total_cmp(b),
+            (FrontmatterKey::Text(a), FrontmatterKey::Text(b)) => natural_cmp(a, b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for FrontmatterKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for FrontmatterKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FrontmatterKey {}
+
+/// Get the value of a top-level `field` in the YAML front matter of a match, e.g., `3` for `order: 3`. Matches without
+/// front matter, or without the field, give back `None`, as do fields whose value is nested or empty. Front matter that
+/// isn't valid YAML is reported, and the match is sorted as if it didn't have the field.
+fn get_frontmatter_field(import_paths: &ImportPaths, field: &str) -> Option<String> {
+    let contents = fs::read_to_string(&import_paths.file_path).ok()?;
+    parse_frontmatter_field(&contents, field).unwrap_or_else(|err| {
+        HANDLER.with(|handler| {
+            handler.warn(&format!(
+                "Import Glob Array Plugin couldn't read the front matter of {}, as it isn't valid YAML ({err})",
+                import_paths.file_path.display()
+            ))
+        });
+        None
+    })
+}
+
+/// Parse the front matter at the start of `contents`, between two `---` lines, as YAML, and get the value of its
+/// top-level `field` as a string.
+fn parse_frontmatter_field(
+    contents: &str,
+    field: &str,
+) -> Result<Option<String>, serde_yaml::Error> {
+    let mut lines = contents.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Ok(None);
+    }
+
+    let mut yaml = String::new();
+    for line in lines {
+        if line.trim_end() == "---" {
+            if yaml.trim().is_empty() {
+                return Ok(None);
+            }
+            let Value::Object(frontmatter) = serde_yaml::from_str(&yaml)? else {
+                return Ok(None);
+            };
+            return Ok(match frontmatter.get(field) {
+                Some(Value::String(value)) if !value.is_empty() => Some(value.to_owned()),
+                Some(value @ (Value::Number(_) | Value::Bool(_))) => Some(value.to_string()),
+                _ => None,
+            });
+        }
+        yaml.push_str(line);
+        yaml.push('\n');
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_frontmatter_field;
+
+    #[test]
+    fn frontmatter_field_is_read_as_yaml() {
+        let contents = "---\r\ntitle: \"Getting started: part 1\"\r\norder : 3\r\ndraft: false\r\n\
+                        summary: |\r\n  First line\r\n  Second line\r\ntags:\r\n  - docs\r\n---\r\n# Body\r\n";
+
+        let field = |field| parse_frontmatter_field(contents, field).unwrap();
+        assert_eq!(field("title").as_deref(), Some("Getting started: part 1"));
+        assert_eq!(field("order").as_deref(), Some("3"));
+        assert_eq!(field("draft").as_deref(), Some("false"));
+        assert_eq!(
+            field("summary").as_deref(),
+            Some("First line\nSecond line\n")
+        );
+        assert_eq!(field("tags"), None);
+        assert_eq!(field("missing"), None);
+    }
+
+    #[test]
+    fn unclosed_or_missing_frontmatter_has_no_fields() {
+        assert_eq!(parse_frontmatter_field("# Title\n", "order").unwrap(), None);
+        assert_eq!(
+            parse_frontmatter_field("---\norder: 1\n", "order").unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_frontmatter_field("---\n---\n", "order").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_frontmatter_is_an_error() {
+        assert!(parse_frontmatter_field("---\norder: [1\n---\n", "order").is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+/// Check whether a pattern is a bare specifier, i.e., it globs over the files of an installed package, such as
+/// `date-fns/locale/*/index.js`, rather than a path or a URL.
+pub(crate) fn is_package_pattern(pattern: &str) -> bool {
+    let name = pattern.split('/').next().unwrap_or_default();
+    !name.is_empty() && name != "@" && !name.starts_with('.') && !name.contains(':')
+}
+
+/// Get the bare specifier that imports a file of an installed package, e.g., `date-fns/locale/en-US` for
+/// `node_modules/date-fns/locale/en-US/index.js`. Files in a package's own `node_modules` belong to the innermost
+/// package, and a match that is the root of a package (e.g., for `@acme/*`) imports the package itself.
+///
+/// When the package has an `exports` field, only the subpaths it exports can be imported, so the specifier is derived
+/// from the first export (including subpath patterns) whose target, resolved with `conditions`, is the file. Files that
+/// aren't exported, and directories that aren't packages, give back `None`. Otherwise, the file's path within the
+/// package is used as-is.
+pub(crate) fn get_package_specifier(
+    node_modules: &Path,
+    file_path: &Path,
+    conditions: &[String],
+) -> Option<String> {
+    let relative_path = file_path.strip_prefix(node_modules).ok()?.to_str()?;
+    let package_path = relative_path
+        .rsplit_once("/node_modules/")
+        .map_or(relative_path, |(_, package_path)| package_path);
+    let (name, subpath) = split_package_path(package_path)?;
+    let package_dir = file_path
+        .ancestors()
+        .nth(Path::new(subpath).components().count())?;
+
+    let package_json = fs::read_to_string(package_dir.join("package.json")).ok();
+    if subpath.is_empty() {
+        package_json.as_ref()?;
+    } else if file_path.is_dir() {
+        return None;
+    }
+    let exports = package_json
+        .and_then(|package_json| serde_json::from_str::<Value>(&package_json).ok())
+        .and_then(|package_json| package_json.get("exports").cloned());
+
+    let Some(exports) = exports else {
+        return Some(format!("{name}/{subpath}").trim_end_matches('/').to_owned());
+    };
+
+    get_exported_subpaths(exports)
+        .into_iter()
+        .find_map(|(key, target)| {
+            let target = resolve_export_target(&target, conditions)?;
+            let exported_subpath = match subpath {
+                "" if key == "." => key,
+                _ => match_export(&key, target.strip_prefix("./")?, subpath)?,
+            };
+            Some(format!(
+                "{name}{}",
+                exported_subpath.trim_start_matches('.')
+            ))
+        })
+}
+
+/// Get the entry point of a package directory, such as a workspace package matched by `./packages/*/`. Like Node.js,
+/// the `.` export (resolved with `conditions`) wins over `main`, which falls back to `index.js`. A `main` without an
+/// extension is probed as a `.js` file and then as a directory with an `index.js`. Directories without a
+/// `package.json`, and entry points that don't exist, give back `None`.
+pub(crate) fn get_package_entry(package_dir: &Path, conditions: &[String]) -> Option<PathBuf> {
+    let package_json = fs::read_to_string(package_dir.join("package.json")).ok()?;
+    let package_json = serde_json::from_str::<Value>(&package_json).ok()?;
+
+    if let Some(exports) = package_json.get("exports").cloned() {
+        let target = get_exported_subpaths(exports)
+            .get(".")
+            .and_then(|target| resolve_export_target(target, conditions))?;
+        let entry = package_dir.join(target.trim_start_matches("./"));
+        return entry.is_file().then_some(entry);
+    }
+
+    let main = package_json
+        .get("main")
+        .and_then(Value::as_str)
+        .unwrap_or("index.js")
+        .trim_start_matches("./");
+    let entry = package_dir.join(main);
+    [
+        entry.to_owned(),
+        entry.with_extension("js"),
+        entry.join("index.js"),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+}
+
+/// Split a path, relative to `node_modules`, into the name of the package it belongs to (including its scope) and the
+/// path within the package. Paths of scope directories give back `None`.
+fn split_package_path(path: &str) -> Option<(&str, &str)> {
+    let mut separators = path.match_indices('/').map(|(index, _)| index);
+    let name_end = if path.starts_with('@') {
+        separators
+            .nth(1)
+            .or_else(|| path.contains('/').then_some(path.len()))?
+    } else {
+        separators.next().unwrap_or(path.len())
+    };
+
+    Some((&path[..name_end], path[name_end..].trim_start_matches('/')))
+}
+
+/// Normalize an `exports` field to its subpath exports, since a string, array, or object of conditions is shorthand
+/// for the `.` subpath.
+fn get_exported_subpaths(exports: Value) -> Map<String, Value> {
+    match exports {
+        Value::Object(subpaths) if subpaths.keys().all(|key| key.starts_with('.')) => subpaths,
+        exports => Map::from_iter([(".".to_owned(), exports)]),
+    }
+}
+
+/// Match a file's `subpath` within its package against an export's `key` and resolved `target`, and give back the
+/// subpath that exports the file, e.g., `./locale/en-US` for the `./locale/*` key and the `locale/*/index.js` target.
+fn match_export(key: &str, target: &str, subpath: &str) -> Option<String> {
+    match (key.split_once('*'), target.split_once('*')) {
+        (Some((key_prefix, key_suffix)), Some((target_prefix, target_suffix))) => {
+            let captured = subpath
+                .strip_prefix(target_prefix)?
+                .strip_suffix(target_suffix)?;
+            Some(format!("{key_prefix}{captured}{key_suffix}"))
+        }
+        (None, None) if target == subpath => Some(key.to_owned()),
+        _ => None,
+    }
+}
+
+/// Resolve the target of an export, which is either a path, an array of fallbacks, or an object of conditions that is
+/// checked in order, where `default` always matches. Excluded (`null`) targets give back `None`.
+fn resolve_export_target(target: &Value, conditions: &[String]) -> Option<String> {
+    match target {
+        Value::String(target) => Some(target.to_owned()),
+        Value::Array(targets) => targets
+            .iter()
+            .find_map(|target| resolve_export_target(target, conditions)),
+        Value::Object(conditional_targets) => {
+            conditional_targets.iter().find_map(|(condition, target)| {
+                (condition == "default" || conditions.contains(condition))
+                    .then(|| resolve_export_target(target, conditions))
+                    .flatten()
+            })
+        }
+        _ => None,
+    }
+}
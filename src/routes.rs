@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use swc_core::ecma::ast::{Expr, ImportSpecifier as SWCImportSpecifier};
+
+use crate::imports::ImportSpecifier;
+use crate::utils::{
+    get_arrow_expr, get_ident_expr, get_member_expr, get_method_call_expr, get_object_expr,
+    get_str_expr,
+};
+use crate::ImportPaths;
+
+const PASSTHROUGH_EXPORTS: [&str; 2] = ["loader", "action"];
+
+/// The named exports of a route module that were requested alongside the array of routes, and get passed through to
+/// React Router.
+pub(crate) struct RouteExports(Vec<&'static str>);
+
+impl RouteExports {
+    pub(crate) fn from_specifiers(specifiers: &[ImportSpecifier]) -> Self {
+        RouteExports(
+            PASSTHROUGH_EXPORTS
+                .into_iter()
+                .filter(|export| {
+                    specifiers.iter().any(|specifier| {
+                        matches!(specifier.as_ref(), SWCImportSpecifier::Named(_))
+                            && specifier.get_imported_name().as_deref() == Some(*export)
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Get an [Expr](Expr) that contains a React Router route object for a match, i.e.,
+/// `{ path: "/users/:id", lazy: () => import("./routes/users/$id.tsx") }`, where `import_expr` is the dynamic
+/// `import()` of the match.
+///
+/// When the routes were imported through a namespace specifier, the route module is handed to React Router as-is.
+/// Otherwise, the module's default export is used as the route's `Component`, and any requested [RouteExports]
+/// are passed through alongside it.
+pub(crate) fn get_route_expr(
+    import_paths: &ImportPaths,
+    import_expr: Expr,
+    exports: &RouteExports,
+    namespace: bool,
+) -> Expr {
+    let lazy_body = if namespace {
+        import_expr
+    } else {
+        let mut props = vec![("Component", get_member_expr(get_ident_expr("m"), "default"))];
+        exports
+            .0
+            .iter()
+            .for_each(|export| props.push((*export, get_member_expr(get_ident_expr("m"), export))));
+
+        get_method_call_expr(
+            import_expr,
+            "then",
+            vec![get_arrow_expr(vec!["m"], get_object_expr(props))],
+        )
+    };
+
+    get_object_expr(vec![
+        (
+            "path",
+            get_str_expr(get_route_path(&import_paths.relative_to_glob)),
+        ),
+        ("lazy", get_arrow_expr(vec![], lazy_body)),
+    ])
+}
+
+/// Derive a route path from a file's location relative to the glob's base directory, following the Remix file naming
+/// conventions: `users/$id.tsx` becomes `/users/:id`, `files/$.tsx` becomes `/files/*`, and `index` files map to
+/// their parent directory.
+pub(crate) fn get_route_path(relative_to_glob: &Path) -> String {
+    let mut segments: Vec<String> = relative_to_glob
+        .with_extension("")
+        .iter()
+        .map(|segment| segment.to_string_lossy().into_owned())
+        .collect();
+
+    if segments.last().map(String::as_str) == Some("index") {
+        segments.pop();
+    }
+
+    let segments: Vec<String> = segments
+        .into_iter()
+        .map(|segment| match segment.strip_prefix('$') {
+            Some("") => "*".to_owned(),
+            Some(param) => format!(":{param}"),
+            None => segment,
+        })
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
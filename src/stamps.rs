@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use crate::utils::update_json_file;
+
+/// A pattern whose matches changed since the stamp file was last written.
+pub(crate) struct ChangedPattern {
+    pub(crate) pattern: String,
+    pub(crate) previous_count: u64,
+    pub(crate) count: u64,
+}
+
+/// Get a fingerprint of a pattern's `matches`, which only changes when a file is added to or removed from them, i.e.,
+/// the FNV-1a hash of the sorted paths.
+fn get_fingerprint(matches: &[Value]) -> String {
+    let mut paths: Vec<&str> = matches.iter().filter_map(Value::as_str).collect();
+    paths.sort_unstable();
+
+    let hash = paths.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, path| {
+        path.bytes().chain([0]).fold(hash, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+        })
+    });
+    format!("{hash:016x}")
+}
+
+/// Replace the entry of `importer` in the stamp file at `stamp_path` with a fingerprint of the matches of each pattern
+/// it expanded, and give back the patterns whose fingerprint differs from the one that was recorded before. Patterns
+/// that weren't recorded before aren't compared, so a first build doesn't report anything.
+pub(crate) fn update_stamp_entry(
+    stamp_path: &Path,
+    importer: &str,
+    patterns: &Map<String, Value>,
+) -> Result<Vec<ChangedPattern>, String> {
+    let stamps: Map<String, Value> = patterns
+        .iter()
+        .map(|(pattern, matches)| {
+            let matches = matches.as_array().map(Vec::as_slice).unwrap_or_default();
+            let stamp = json!({ "count": matches.len(), "fingerprint": get_fingerprint(matches) });
+            (pattern.to_owned(), stamp)
+        })
+        .collect();
+    let mut changed = vec![];
+
+    update_json_file(stamp_path, |entries| {
+        if let Some(previous) = entries.get(importer).and_then(Value::as_object) {
+            changed = stamps
+                .iter()
+                .filter_map(|(pattern, stamp)| {
+                    let previous = previous.get(pattern)?;
+                    if previous.get("fingerprint") == stamp.get("fingerprint") {
+                        return None;
+                    }
+                    Some(ChangedPattern {
+                        pattern: pattern.to_owned(),
+                        previous_count: get_count(previous),
+                        count: get_count(stamp),
+                    })
+                })
+                .collect();
+        }
+
+        if stamps.is_empty() {
+            entries.remove(importer);
+        } else {
+            entries.insert(importer.to_owned(), Value::Object(stamps));
+        }
+    })?;
+    Ok(changed)
+}
+
+/// Get the number of matches that a stamp was recorded with.
+fn get_count(stamp: &Value) -> u64 {
+    stamp
+        .get("count")
+        .and_then(Value::as_u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map, Value};
+
+    use super::update_stamp_entry;
+    use crate::test_utils::temp_dir;
+
+    fn patterns(matches: &[&str]) -> Map<String, Value> {
+        let mut patterns = Map::new();
+        patterns.insert("./docs/*.md".to_owned(), json!(matches));
+        patterns
+    }
+
+    #[test]
+    fn first_write_reports_nothing() {
+        let stamp_path = temp_dir("stamps-first-write").join("stamps.json");
+
+        let changed =
+            update_stamp_entry(&stamp_path, "index.js", &patterns(&["docs/a.md"])).unwrap();
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn added_match_is_reported() {
+        let stamp_path = temp_dir("stamps-added-match").join("stamps.json");
+
+        update_stamp_entry(&stamp_path, "index.js", &patterns(&["docs/a.md"])).unwrap();
+        let changed = update_stamp_entry(
+            &stamp_path,
+            "index.js",
+            &patterns(&["docs/a.md", "docs/b.md"]),
+        )
+        .unwrap();
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].pattern, "./docs/*.md");
+        assert_eq!(changed[0].previous_count, 1);
+        assert_eq!(changed[0].count, 2);
+    }
+
+    #[test]
+    fn reordered_matches_are_not_reported() {
+        let stamp_path = temp_dir("stamps-reordered-matches").join("stamps.json");
+
+        update_stamp_entry(
+            &stamp_path,
+            "index.js",
+            &patterns(&["docs/a.md", "docs/b.md"]),
+        )
+        .unwrap();
+        let changed = update_stamp_entry(
+            &stamp_path,
+            "index.js",
+            &patterns(&["docs/b.md", "docs/a.md"]),
+        )
+        .unwrap();
+
+        assert!(changed.is_empty());
+    }
+}
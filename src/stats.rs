@@ -0,0 +1,115 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::json;
+use swc_core::ecma::ast::{Expr, Module, ModuleDecl, Pat, Stmt};
+use swc_core::ecma::visit::{Visit, VisitWith};
+
+use crate::utils::update_json_file;
+
+/// Counts the expressions, patterns, statements, and module declarations of a module, which is a cheap measure of how
+/// much code it contains.
+#[derive(Default)]
+struct NodeCounter {
+    count: usize,
+}
+
+impl Visit for NodeCounter {
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.count += 1;
+        expr.visit_children_with(self);
+    }
+
+    fn visit_module_decl(&mut self, decl: &ModuleDecl) {
+        self.count += 1;
+        decl.visit_children_with(self);
+    }
+
+    fn visit_pat(&mut self, pat: &Pat) {
+        self.count += 1;
+        pat.visit_children_with(self);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.count += 1;
+        stmt.visit_children_with(self);
+    }
+}
+
+/// Statistics of transforming a single file.
+pub(crate) struct FileStats {
+    pub(crate) duration: Duration,
+    pub(crate) generated_nodes: usize,
+    pub(crate) globs: usize,
+    pub(crate) matches: usize,
+}
+
+/// Count the nodes of `module`. See [NodeCounter](NodeCounter).
+pub(crate) fn count_nodes(module: &Module) -> usize {
+    let mut counter = NodeCounter::default();
+    module.visit_with(&mut counter);
+    counter.count
+}
+
+/// Replace the entry of `importer` in the stats file at `stats_path` with the statistics of its latest transform.
+pub(crate) fn write_stats_entry(
+    stats_path: &Path,
+    importer: &str,
+    stats: FileStats,
+) -> Result<(), String> {
+    let entry = json!({
+        "durationMs": stats.duration.as_secs_f64() * 1000.0,
+        "generatedNodes": stats.generated_nodes,
+        "globs": stats.globs,
+        "matches": stats.matches,
+    });
+
+    update_json_file(stats_path, |entries| {
+        entries.insert(importer.to_owned(), entry);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use swc_core::common::SourceMap;
+
+    use super::{count_nodes, write_stats_entry, FileStats};
+    use crate::test_utils::{parse_module, temp_dir};
+
+    #[test]
+    fn stats_entry_describes_the_transform() {
+        let stats_path = temp_dir("stats").join("stats.json");
+        let cm = SourceMap::default();
+        let input = parse_module(&cm, "import docs from \"./docs/*.md\";");
+        let output = parse_module(
+            &cm,
+            "import _iga1 from \"./docs/a.md\"; import _iga2 from \"./docs/b.md\"; const docs = [_iga1, _iga2];",
+        );
+        let stats = FileStats {
+            duration: Duration::from_millis(5),
+            generated_nodes: count_nodes(&output) - count_nodes(&input),
+            globs: 1,
+            matches: 2,
+        };
+
+        write_stats_entry(&stats_path, "src/index.js", stats).unwrap();
+
+        let entries: Value =
+            serde_json::from_str(&fs::read_to_string(&stats_path).unwrap()).unwrap();
+        assert_eq!(
+            entries,
+            json!({
+                "src/index.js": {
+                    "durationMs": 5.0,
+                    "generatedNodes": 6,
+                    "globs": 1,
+                    "matches": 2,
+                }
+            })
+        );
+    }
+}
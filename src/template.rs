@@ -0,0 +1,82 @@
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+use crate::utils::to_pascal_case;
+
+/// Render a template over the parts of a match's path relative to the glob's base directory. For `guides/intro.md`,
+/// the following placeholders are replaced:
+///
+/// * `{name}`, the file name without its extension: `intro`.
+/// * `{basename}`, the file name up to its first dot, which also drops suffixes such as `.module` of
+///   `Button.module.css`: `intro`.
+/// * `{ext}`, the extension without its leading dot: `md`.
+/// * `{dir}`, the directory of the match: `guides`.
+/// * `{path}`, the whole path without the extension: `guides/intro`.
+///
+/// Placeholders can be followed by filters that transform their value, e.g., `{name|pascal}`:
+///
+/// * `pascal`, PascalCase: `Intro`.
+/// * `camel`, camelCase: `intro`.
+/// * `lower` and `upper`, lowercase and uppercase: `intro` and `INTRO`.
+///
+/// Unknown placeholders, and placeholders with unknown filters, are kept as-is.
+pub(crate) fn render_template(template: &str, relative_to_glob: &Path) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+
+        match get_placeholder_value(&rest[1..end], relative_to_glob) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+fn get_placeholder_value(placeholder: &str, relative_to_glob: &Path) -> Option<String> {
+    let mut filters = placeholder.split('|').map(str::trim);
+    let value = get_part_value(filters.next()?, relative_to_glob)?;
+
+    filters.try_fold(value, |value, filter| match filter {
+        "pascal" => Some(to_pascal_case(&value)),
+        "camel" => {
+            let pascal_case = to_pascal_case(&value);
+            let mut chars = pascal_case.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        }
+        "lower" => Some(value.to_lowercase()),
+        "upper" => Some(value.to_uppercase()),
+        _ => None,
+    })
+}
+
+fn get_part_value(part: &str, relative_to_glob: &Path) -> Option<String> {
+    let value = match part {
+        "name" => relative_to_glob.file_stem().map(OsStr::to_os_string),
+        "basename" => relative_to_glob
+            .file_name()
+            .and_then(OsStr::to_str)
+            .and_then(|file_name| file_name.split('.').next())
+            .map(OsString::from),
+        "ext" => relative_to_glob.extension().map(OsStr::to_os_string),
+        "dir" => relative_to_glob
+            .parent()
+            .map(|dir| dir.as_os_str().to_os_string()),
+        "path" => Some(relative_to_glob.with_extension("").into_os_string()),
+        _ => return None,
+    };
+
+    Some(value.unwrap_or_default().to_string_lossy().into_owned())
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use swc_core::common::errors::{DiagnosticBuilder, Emitter, Handler, HANDLER};
+use swc_core::common::{FileName, SourceMap};
+use swc_core::ecma::ast::{EsVersion, Module};
+use swc_core::ecma::parser::{parse_file_as_module, Syntax};
+
+/// Collects the message of each diagnostic that is emitted.
+struct MessageEmitter(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for MessageEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push(db.message());
+    }
+}
+
+/// Run `f` with a [HANDLER](HANDLER) set, and give back its result along with the message of each diagnostic that it
+/// reported.
+pub(crate) fn capture_diagnostics<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let messages = Arc::new(Mutex::new(vec![]));
+    let handler =
+        Handler::with_emitter(true, false, Box::new(MessageEmitter(Arc::clone(&messages))));
+    let result = HANDLER.set(&handler, f);
+    drop(handler);
+
+    let messages = messages.lock().unwrap().clone();
+    (result, messages)
+}
+
+/// Get an empty directory that is only used by the test `name`.
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("import-glob-array-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Parse `src` as a JavaScript module, whose source file is added to `cm`.
+pub(crate) fn parse_module(cm: &SourceMap, src: &str) -> Module {
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    parse_file_as_module(
+        &fm,
+        Syntax::Es(Default::default()),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )
+    .unwrap()
+}
@@ -1,127 +1,1532 @@
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::ffi::OsStr;
+use std::fs;
+use std::iter;
+use std::path::{Path, PathBuf};
 
-use glob::glob;
+use serde_json::Value;
+use swc_core::common::comments::Comments;
+use swc_core::common::errors::HANDLER;
 use swc_core::common::DUMMY_SP;
 use swc_core::ecma::ast::{
-    BindingIdent, Expr, ExprOrSpread, Ident, ImportDecl, ImportDefaultSpecifier,
-    ImportNamedSpecifier, ImportSpecifier as SWCImportSpecifier, ImportStarAsSpecifier, Pat, Str,
-    VarDecl,
+    ArrayLit, ArrowExpr, BinExpr, BinaryOp, BlockStmt, BlockStmtOrExpr, CallExpr, Callee,
+    ComputedPropName, Expr, ExprOrSpread, ExprStmt, FnDecl, Function, Ident, IfStmt, ImportDecl,
+    ImportDefaultSpecifier, ImportNamedSpecifier, ImportSpecifier as SWCImportSpecifier,
+    ImportStarAsSpecifier, MemberExpr, MemberProp, Param, ParenExpr, Pat, ReturnStmt, Stmt, Str,
+    SwitchCase, SwitchStmt, ThrowStmt, TsTypeAliasDecl, TsTypeAnn, VarDecl,
 };
+use tracing::instrument;
 
+use crate::config::{
+    Config, ImportStyle, InlineOverflow, Mode, Prefetch, Sort, SortOrder, WasmImport,
+};
+use crate::filter::{is_junk_path, is_test_path, PathFilter};
+use crate::group::get_grouped_expr;
 use crate::imports::ImportSpecifier;
-use crate::utils::{get_import_map_expr, to_var_decls, upsert_map};
-use crate::ImportGlobArrayPlugin;
+use crate::meta::{get_meta_expr, FrontmatterKey};
+use crate::packages::{get_package_entry, get_package_specifier, is_package_pattern};
+use crate::routes::{get_route_expr, RouteExports};
+use crate::utils::{
+    get_arrow_expr, get_async_iife_expr, get_await_expr, get_call_expr, get_ident_expr,
+    get_ident_pat, get_json_expr, get_member_expr, get_method_call_expr, get_module_url_expr,
+    get_new_expr, get_normalized_path, get_object_expr, get_str_expr, get_str_union_type_alias,
+    get_var_decl, glob_base, parse_ts_type, to_nfc, to_pascal_case, to_var_decls, upsert_map,
+    CREATE_REQUIRE_NAME, RUNTIME_SPECIFIER,
+};
+use crate::{ImportGlobArrayPlugin, ImportPaths};
 
+#[derive(Default)]
 pub(crate) struct TransformedStatements {
+    pub(crate) context_dependencies: Vec<String>,
+    pub(crate) exports: Vec<VarDecl>,
     pub(crate) imports: Vec<ImportDecl>,
+    pub(crate) lookups: Vec<FnDecl>,
     pub(crate) meta: Vec<VarDecl>,
     pub(crate) names: Vec<VarDecl>,
+    pub(crate) requires: Vec<VarDecl>,
+    pub(crate) types: Vec<TsTypeAliasDecl>,
+    pub(crate) warnings: Vec<Stmt>,
 }
-/// Expand the glob pattern embedded within an [ImportDecl](ImportDecl), and give back a tuple of three (3) values:
+
+/// Local name of the runtime helper that picks an export from a dynamically imported module.
+pub(crate) const RUNTIME_PICK_NAME: &str = "_igaPick";
+
+/// Local name of the runtime helper that wraps the error of a failed dynamic import.
+pub(crate) const RUNTIME_WRAP_IMPORT_ERROR_NAME: &str = "_igaWrapImportError";
+
+type ElementMap = Vec<(Pat, Vec<Option<ExprOrSpread>>)>;
+
+/// Expand the glob `pattern` of an [ImportDecl](ImportDecl), and give back the statements that replace it:
+///
+/// * `imports`, a vector of [ImportDecl](ImportDecl), with each item as the expanded representation of the original
+///   glob pattern. This vector is empty for modes that don't import matches statically.
 ///
-/// * The first, a vector of [ImportDecl](ImportDecl), with each item as the expanded representation of the original
-///   glob pattern.
+/// * `requires`, a vector of [VarDecl](VarDecl), with each item as a `require()` of a match. This vector is only used
+///   when matches are imported as CommonJS modules.
 ///
-/// * The second, a vector of [VarDecl](VarDecl), with each item as an [ArrayLit](swc_core::ecma::ast::ArrayLit) that
-///   contains each expanded import that was previously assigned to the variable.
+/// * `names`, a vector of [VarDecl](VarDecl), with each item as an [ArrayLit](swc_core::ecma::ast::ArrayLit) that
+///   contains an element for each match that was previously assigned to the variable. With `count_only`, each item
+///   is the number of matches instead, and every other vector is empty.
 ///
-/// * The third, a vector of [VarDecl](VarDecl), with each item as an [ArrayLit](swc_core::ecma::ast::ArrayLit) that
+/// * `lookups`, a vector of [FnDecl](FnDecl), with each item as a function that switches over the imported paths of the
+///   matches and returns the element of a name, when `lookup_helper` is set. This vector may be empty.
+///
+/// * `meta`, a vector of [VarDecl](VarDecl), with each item as an [ArrayLit](swc_core::ecma::ast::ArrayLit) that
 ///   contains an embedded object for the special `_importMeta` token. This vector may be empty.
-pub(crate) fn transform_import_decl(
-    plugin: &ImportGlobArrayPlugin,
-    import_src: Box<Str>,
+///
+/// * `exports`, a vector of [VarDecl](VarDecl) that get exported from the module, such as the manifest of specifiers
+///   for [Prefetch::Manifest](Prefetch::Manifest). This vector may be empty.
+///
+/// * `types`, a vector of [TsTypeAliasDecl](TsTypeAliasDecl) with the union type of every imported path, when
+///   `path_type` is set and the module is TypeScript. This vector may be empty.
+///
+/// * `warnings`, a vector of [Stmt](Stmt) that warn at runtime in development when a binding turns out to be empty,
+///   when `warn_on_empty` is set. This vector may be empty.
+#[instrument(level = "info", skip_all, fields(pattern = %pattern))]
+pub(crate) fn transform_import_decl<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    pattern: &str,
     import_specifiers: Vec<SWCImportSpecifier>,
 ) -> Option<TransformedStatements> {
-    let glob_path = PathBuf::from_str("/cwd")
-        .unwrap_or_default()
-        .join(&plugin.filename)
-        .with_file_name(import_src.value.to_string().trim_start_matches(&['.', '/']));
-    let glob_path = glob_path.to_str()?;
+    let (mut matches, context_dependencies) = get_matches(plugin, config, pattern)?;
+    let matches_config = get_matches_config(config, &matches);
+    let config = &*matches_config;
+    if config.warn_on_empty && matches.is_empty() {
+        HANDLER.with(|handler| handler.warn(&format!("\"{pattern}\" didn't match any files")));
+    }
+    matches.extend(get_injected_entries(plugin, config));
 
-    let mut name_placeholder_map: HashMap<Pat, Vec<Option<ExprOrSpread>>> = HashMap::new();
-    let mut import_meta_map: HashMap<Pat, Vec<Option<ExprOrSpread>>> = HashMap::new();
-
-    let glob_pattern = glob(glob_path).ok()?;
-    let import_statements: Vec<ImportDecl> = glob_pattern
-        .map(|result| match result {
-            Ok(file_path) => {
-                let import_paths = plugin.get_paths(&file_path)?;
-                let specifiers: Vec<SWCImportSpecifier> =
-                    import_specifiers.iter().fold(vec![], |mut acc, specifier| {
-                        let specifier: ImportSpecifier = specifier.to_owned().into();
-                        let name_ident = Pat::Ident(BindingIdent {
-                            id: Ident::new(specifier.get_local_name().into(), DUMMY_SP),
-                            type_ann: None,
-                        });
-
-                        if specifier.is_meta_decl().unwrap_or(false) {
-                            upsert_map(
-                                &mut import_meta_map,
-                                &name_ident,
-                                get_import_map_expr(&import_paths),
-                            );
-                            return acc;
-                        }
+    if config.count_only {
+        let count = get_json_expr(&Value::from(matches.len()));
+        let names: Vec<VarDecl> = import_specifiers
+            .into_iter()
+            .map(|specifier| {
+                get_var_decl(
+                    ImportSpecifier::from(specifier).get_local_pat(),
+                    count.clone(),
+                )
+            })
+            .collect();
+        return Some(TransformedStatements {
+            context_dependencies,
+            warnings: get_empty_warnings(config, &names, pattern),
+            names,
+            ..Default::default()
+        });
+    }
 
-                        let placeholder = &*plugin.next_id("_iga");
-
-                        upsert_map(
-                            &mut name_placeholder_map,
-                            &name_ident,
-                            ExprOrSpread::from(Box::new(Expr::Ident(Ident::new(
-                                placeholder.into(),
-                                DUMMY_SP,
-                            )))),
-                        );
-
-                        acc.push(match specifier.into_inner() {
-                            SWCImportSpecifier::Default(_) => {
-                                SWCImportSpecifier::Default(ImportDefaultSpecifier {
-                                    local: Ident::new(placeholder.into(), DUMMY_SP),
-                                    span: DUMMY_SP,
-                                })
-                            }
-                            SWCImportSpecifier::Named(named) => {
-                                SWCImportSpecifier::Named(ImportNamedSpecifier {
-                                    imported: named.imported.clone(),
-                                    is_type_only: false,
-                                    local: Ident::new(placeholder.into(), DUMMY_SP),
-                                    span: DUMMY_SP,
-                                })
-                            }
-                            SWCImportSpecifier::Namespace(_) => {
-                                SWCImportSpecifier::Namespace(ImportStarAsSpecifier {
-                                    local: Ident::new(placeholder.into(), DUMMY_SP),
-                                    span: DUMMY_SP,
-                                })
-                            }
-                        });
-                        acc
-                    });
-
-                Some(ImportDecl {
-                    asserts: None,
+    let (meta_specifiers, specifiers): (Vec<ImportSpecifier>, Vec<ImportSpecifier>) =
+        import_specifiers
+            .into_iter()
+            .map(ImportSpecifier::from)
+            .partition(|specifier| specifier.is_meta_decl().unwrap_or(false));
+
+    let mut import_meta_map: ElementMap = vec![];
+    let mut name_placeholder_map: ElementMap = vec![];
+
+    let project_root = plugin.project_root();
+    meta_specifiers.iter().for_each(|specifier| {
+        matches.iter().for_each(|import_paths| {
+            upsert_map(
+                &mut import_meta_map,
+                &specifier.get_local_pat(),
+                get_meta_expr(config, import_paths, &project_root, plugin.is_production()),
+            )
+        })
+    });
+
+    let mut export_statements: Vec<VarDecl> = vec![];
+    let mut import_statements: Vec<ImportDecl> = vec![];
+    let mut require_statements: Vec<VarDecl> = vec![];
+
+    match (config.mode, config.import_style) {
+        // Only the `_importMeta` binding is used, so the matches themselves don't need to be loaded.
+        _ if config.drop_unused && specifiers.is_empty() => {}
+        (Mode::Eager | Mode::Merged, ImportStyle::Esm) => {
+            import_statements = transform_eager(
+                plugin,
+                config,
+                &matches,
+                specifiers,
+                &mut name_placeholder_map,
+            )
+        }
+        (Mode::Eager | Mode::Merged, _) => {
+            require_statements = transform_require(
+                plugin,
+                config,
+                &matches,
+                specifiers,
+                &mut name_placeholder_map,
+            )
+        }
+        (Mode::Raw, _) => {
+            import_statements = transform_raw(
+                plugin,
+                config,
+                &matches,
+                specifiers,
+                &mut name_placeholder_map,
+            )
+        }
+        (Mode::Url, _) => transform_url(
+            plugin,
+            config,
+            &matches,
+            specifiers,
+            &mut name_placeholder_map,
+        ),
+        (Mode::Lazy, _) => {
+            let locals: Vec<String> = specifiers.iter().map(|s| s.get_local_name()).collect();
+            export_statements = get_prefetch_manifests(config, &matches, &locals);
+            transform_lazy(
+                plugin,
+                config,
+                &matches,
+                specifiers,
+                &mut name_placeholder_map,
+            )
+        }
+        (Mode::LazyEager, _) => transform_lazy_eager(
+            plugin,
+            config,
+            &matches,
+            specifiers,
+            &mut name_placeholder_map,
+        ),
+        (Mode::ReactRouterLazy, _) => {
+            let locals: Vec<String> = specifiers
+                .iter()
+                .filter(|s| !matches!(s.as_ref(), SWCImportSpecifier::Named(_)))
+                .map(|s| s.get_local_name())
+                .collect();
+            export_statements = get_prefetch_manifests(config, &matches, &locals);
+            transform_react_router_lazy(
+                plugin,
+                config,
+                &matches,
+                specifiers,
+                &mut name_placeholder_map,
+            )
+        }
+    }
+
+    if config.export_bindings && config.mode == Mode::Eager {
+        export_statements.extend(get_binding_exports(&matches, &name_placeholder_map));
+    }
+
+    if let Some(element_wrapper) = &config.element_wrapper {
+        wrap_elements(
+            config,
+            &matches,
+            &mut name_placeholder_map,
+            element_wrapper,
+            &project_root,
+            plugin.is_production(),
+        );
+        if let Some(source) = &config.element_wrapper_source {
+            plugin.require_named_import(element_wrapper, element_wrapper, source);
+        }
+    }
+
+    let lookups = if config.lookup_helper {
+        get_lookup_fns(&matches, &name_placeholder_map)
+    } else {
+        vec![]
+    };
+
+    let mut names = if config.mode == Mode::Merged {
+        to_merged_var_decls(name_placeholder_map)
+    } else {
+        to_grouped_var_decls(config, &matches, name_placeholder_map)
+    };
+    if config.mode == Mode::Lazy && config.keyed && plugin.is_typescript() {
+        annotate_var_decls(&mut names, &config.get_lazy_type());
+    }
+    let warnings = get_empty_warnings(config, &names, pattern);
+    if config.mode == Mode::LazyEager {
+        export_statements.extend(await_var_decls(plugin, config, &matches, &mut names));
+    }
+
+    let types = match &config.path_type {
+        Some(path_type) if plugin.is_typescript() => vec![get_str_union_type_alias(
+            path_type,
+            matches
+                .iter()
+                .map(|import_paths| import_paths.imported_path.to_owned())
+                .collect(),
+        )],
+        _ => vec![],
+    };
+
+    Some(TransformedStatements {
+        context_dependencies,
+        exports: export_statements,
+        imports: import_statements,
+        lookups,
+        meta: to_grouped_var_decls(config, &matches, import_meta_map),
+        names,
+        requires: require_statements,
+        types,
+        warnings,
+    })
+}
+
+/// Get the config that the `matches` of an import are expanded with, which is the import's own config, except that:
+///
+/// * The [CssModules](Mode::CssModules) mode is expanded as the keyed [Eager](Mode::Eager) mode, keyed by the
+///   `{basename}` of each match unless `key_template` is set.
+///
+/// * Matches that are all `.wasm` files are expanded in the [Url](Mode::Url) mode when `wasm` is
+///   [Url](WasmImport::Url).
+///
+/// * Matches that are all data files (see [is_data_file](is_data_file)) are expanded in the [Raw](Mode::Raw) mode when
+///   `convert_data` is set, so they're inlined as literals.
+fn get_matches_config<'a>(config: &'a Config, matches: &[ImportPaths]) -> Cow<'a, Config> {
+    if config.mode == Mode::CssModules {
+        let default_key_template = Config::default().key_template;
+        return Cow::Owned(Config {
+            keyed: true,
+            key_template: if config.key_template == default_key_template {
+                "{basename}".into()
+            } else {
+                config.key_template.to_owned()
+            },
+            mode: Mode::Eager,
+            ..config.to_owned()
+        });
+    }
+    if matches.is_empty() {
+        return Cow::Borrowed(config);
+    }
+    if config.wasm == WasmImport::Url && matches.iter().all(|import_paths| is_wasm(import_paths)) {
+        return Cow::Owned(Config {
+            mode: Mode::Url,
+            ..config.to_owned()
+        });
+    }
+    if config.convert_data
+        && matches
+            .iter()
+            .all(|import_paths| is_data_file(import_paths))
+    {
+        return Cow::Owned(Config {
+            mode: Mode::Raw,
+            ..config.to_owned()
+        });
+    }
+
+    Cow::Borrowed(config)
+}
+
+/// Check whether a match is a YAML or TOML file, which is parsed into a literal when `convert_data` is set.
+fn is_data_file(import_paths: &ImportPaths) -> bool {
+    matches!(
+        import_paths.file_path.extension().and_then(OsStr::to_str),
+        Some("toml" | "yaml" | "yml")
+    )
+}
+
+/// Check whether a match is a WebAssembly module.
+fn is_wasm(import_paths: &ImportPaths) -> bool {
+    import_paths.file_path.extension() == Some(OsStr::new("wasm"))
+}
+
+/// Check whether a match is a WebAssembly module that is imported through the ES module integration, which doesn't give
+/// it a default export.
+fn is_wasm_module(config: &Config, import_paths: &ImportPaths) -> bool {
+    config.wasm == WasmImport::Module && is_wasm(import_paths)
+}
+
+/// Get a match for each of the `inject_entries` of an import, which are appended after the globbed matches so they're
+/// imported the same way. Relative specifiers resolve against the file being transformed, while others (such as
+/// packages) are used as-is.
+fn get_injected_entries<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+) -> Vec<ImportPaths> {
+    config
+        .inject_entries
+        .iter()
+        .map(|entry| {
+            let (absolute_path, file_path) = if entry.starts_with('.') {
+                let file_path = get_normalized_path(&plugin.importer_dir().join(entry));
+                (
+                    plugin.host_path(&file_path).to_string_lossy().into_owned(),
+                    file_path,
+                )
+            } else {
+                (entry.to_owned(), PathBuf::from(entry))
+            };
+
+            ImportPaths {
+                absolute_path,
+                file_path,
+                imported_path: entry.to_owned(),
+                relative_to_glob: Path::new(entry)
+                    .file_name()
+                    .map(PathBuf::from)
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Get an `if` statement for each binding of `var_decls` that warns, in development builds only, when the binding
+/// turns out to be empty at runtime and `warn_on_empty` is set, i.e., `if (process.env.NODE_ENV !== "production" &&
+/// docs.length === 0) console.warn(...)`. Bindings that aren't arrays, such as keyed objects, aren't checked, and
+/// neither are those of the [LazyEager](Mode::LazyEager) mode without `top_level_await`, since they're only filled once
+/// their imports resolve. With `count_only`, the count itself is checked instead.
+fn get_empty_warnings(config: &Config, var_decls: &[VarDecl], pattern: &str) -> Vec<Stmt> {
+    if !config.warn_on_empty || (config.mode == Mode::LazyEager && !config.top_level_await) {
+        return vec![];
+    }
+
+    var_decls
+        .iter()
+        .flat_map(|var_decl| &var_decl.decls)
+        .filter_map(|decl| {
+            let Pat::Ident(binding) = &decl.name else {
+                return None;
+            };
+            let binding_expr = Expr::Ident(binding.id.clone());
+            let length_expr = match decl.init.as_deref() {
+                _ if config.count_only => binding_expr,
+                Some(Expr::Array(_)) => get_member_expr(binding_expr, "length"),
+                _ => return None,
+            };
+
+            let warn_expr = get_method_call_expr(
+                get_ident_expr("console"),
+                "warn",
+                vec![get_str_expr(format!(
+                    "import-glob-array: \"{pattern}\" didn't match any files"
+                ))],
+            );
+            let is_development_expr = Expr::Bin(BinExpr {
+                left: Box::new(get_member_expr(
+                    get_member_expr(get_ident_expr("process"), "env"),
+                    "NODE_ENV",
+                )),
+                op: BinaryOp::NotEqEq,
+                right: Box::new(get_str_expr("production".into())),
+                span: DUMMY_SP,
+            });
+            let is_empty_expr = Expr::Bin(BinExpr {
+                left: Box::new(length_expr),
+                op: BinaryOp::EqEqEq,
+                right: Box::new(get_json_expr(&Value::from(0))),
+                span: DUMMY_SP,
+            });
+
+            Some(Stmt::If(IfStmt {
+                alt: None,
+                cons: Box::new(Stmt::Block(BlockStmt {
                     span: DUMMY_SP,
-                    specifiers,
-                    src: Box::new(Str {
-                        raw: None,
+                    stmts: vec![Stmt::Expr(ExprStmt {
+                        expr: Box::new(warn_expr),
                         span: DUMMY_SP,
-                        value: import_paths.imported_path.into(),
-                    }),
-                    type_only: false,
+                    })],
+                })),
+                span: DUMMY_SP,
+                test: Box::new(Expr::Bin(BinExpr {
+                    left: Box::new(is_development_expr),
+                    op: BinaryOp::LogicalAnd,
+                    right: Box::new(is_empty_expr),
+                    span: DUMMY_SP,
+                })),
+            }))
+        })
+        .collect()
+}
+
+/// Annotate the binding of each [VarDecl](VarDecl) with the TypeScript type `ts_type`.
+fn annotate_var_decls(var_decls: &mut [VarDecl], ts_type: &str) {
+    let Some(ts_type) = parse_ts_type(ts_type) else {
+        return;
+    };
+
+    var_decls
+        .iter_mut()
+        .flat_map(|var_decl| var_decl.decls.iter_mut())
+        .for_each(|decl| {
+            if let Pat::Ident(binding) = &mut decl.name {
+                binding.type_ann = Some(Box::new(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: ts_type.clone(),
+                }));
+            }
+        });
+}
+
+/// Await the arrays of dynamic imports that [transform_lazy_eager](transform_lazy_eager) generated for each binding,
+/// with `await Promise.all([...])`. Without `top_level_await`, each binding starts out as an empty array instead,
+/// which an async IIFE fills once every import resolves, and the IIFE's promise is given back as a [VarDecl](VarDecl)
+/// to export, e.g., `export const docsReady`.
+///
+/// With `all_settled`, the imports are awaited with `Promise.allSettled()` instead, and the `_importMeta` object of
+/// each match is added to its result, e.g., `{ status: "rejected", reason, meta }`. The `_importMeta` objects are
+/// declared once, ahead of the bindings, e.g., `const _igaMeta1 = [...]`.
+fn await_var_decls<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    var_decls: &mut Vec<VarDecl>,
+) -> Vec<VarDecl> {
+    let meta_name = config
+        .all_settled
+        .then(|| plugin.next_id(config, "_igaMeta"));
+
+    let ready_var_decls = var_decls
+        .iter_mut()
+        .flat_map(|var_decl| var_decl.decls.iter_mut())
+        .filter_map(|decl| {
+            let imports = *decl.init.take()?;
+            let all_expr = if let Some(meta_name) = &meta_name {
+                get_settled_expr(imports, meta_name)
+            } else {
+                get_await_expr(get_method_call_expr(
+                    get_ident_expr("Promise"),
+                    "all",
+                    vec![imports],
+                ))
+            };
+
+            if config.top_level_await {
+                decl.init = Some(Box::new(all_expr));
+                return None;
+            }
+
+            let Pat::Ident(binding) = &decl.name else {
+                return None;
+            };
+            let local = binding.id.sym.to_string();
+            decl.init = Some(Box::new(Expr::Array(ArrayLit {
+                elems: vec![],
+                span: DUMMY_SP,
+            })));
+
+            let push_expr = Expr::Call(CallExpr {
+                args: vec![ExprOrSpread {
+                    expr: Box::new(all_expr),
+                    spread: Some(DUMMY_SP),
+                }],
+                callee: Callee::Expr(Box::new(get_member_expr(get_ident_expr(&local), "push"))),
+                span: DUMMY_SP,
+                type_args: None,
+            });
+            Some(get_var_decl(
+                get_ident_pat(&format!("{local}Ready")),
+                get_async_iife_expr(vec![Stmt::Expr(ExprStmt {
+                    expr: Box::new(push_expr),
+                    span: DUMMY_SP,
+                })]),
+            ))
+        })
+        .collect();
+
+    if let Some(meta_name) = meta_name {
+        let meta_expr = Expr::Array(ArrayLit {
+            elems: matches
+                .iter()
+                .map(|import_paths| {
+                    Some(get_meta_expr(
+                        config,
+                        import_paths,
+                        &plugin.project_root(),
+                        plugin.is_production(),
+                    ))
                 })
+                .collect(),
+            span: DUMMY_SP,
+        });
+        var_decls.insert(0, get_var_decl(get_ident_pat(&meta_name), meta_expr));
+    }
+    ready_var_decls
+}
+
+/// Get an [Expr](Expr) that awaits `imports` with `Promise.allSettled()`, and adds the `_importMeta` object of each
+/// match, which are declared as `meta_name`, to its result, i.e., `(await Promise.allSettled([...])).map((result, i) =>
+/// Object.assign(result, { meta: _igaMeta1[i] }))`.
+fn get_settled_expr(imports: Expr, meta_name: &str) -> Expr {
+    let settled_expr = Expr::Paren(ParenExpr {
+        expr: Box::new(get_await_expr(get_method_call_expr(
+            get_ident_expr("Promise"),
+            "allSettled",
+            vec![imports],
+        ))),
+        span: DUMMY_SP,
+    });
+    let match_meta_expr = Expr::Member(MemberExpr {
+        obj: Box::new(get_ident_expr(meta_name)),
+        prop: MemberProp::Computed(ComputedPropName {
+            expr: Box::new(get_ident_expr("i")),
+            span: DUMMY_SP,
+        }),
+        span: DUMMY_SP,
+    });
+
+    get_method_call_expr(
+        settled_expr,
+        "map",
+        vec![get_arrow_expr(
+            vec!["result", "i"],
+            get_method_call_expr(
+                get_ident_expr("Object"),
+                "assign",
+                vec![
+                    get_ident_expr("result"),
+                    get_object_expr(vec![("meta", match_meta_expr)]),
+                ],
+            ),
+        )],
+    )
+}
+
+/// Replace each element with a call to the configured `element_wrapper`, which is given the element and the match's
+/// `_importMeta` object, e.g., `defineRoute(_iga1, { absolutePath: ..., importedPath: ... })`.
+fn wrap_elements(
+    config: &Config,
+    matches: &[ImportPaths],
+    map: &mut ElementMap,
+    element_wrapper: &str,
+    project_root: &Path,
+    is_production: bool,
+) {
+    map.iter_mut().for_each(|(_, elems)| {
+        elems
+            .iter_mut()
+            .zip(matches)
+            .for_each(|(slot, import_paths)| {
+                if let Some(elem) = slot.take() {
+                    let meta = get_meta_expr(config, import_paths, project_root, is_production);
+                    *slot = Some(ExprOrSpread::from(get_call_expr(
+                        element_wrapper,
+                        vec![*elem.expr, *meta.expr],
+                    )));
+                }
+            })
+    });
+}
+
+/// Transform the elements that were generated for each name into a [VarDecl](VarDecl) that merges them into a single
+/// object, i.e., `Object.assign({}, _iga1, _iga2)`, where later matches win when they have an export of the same name.
+fn to_merged_var_decls(map: ElementMap) -> Vec<VarDecl> {
+    map.into_iter()
+        .map(|(name, elems)| {
+            let args = iter::once(get_object_expr(vec![]))
+                .chain(elems.into_iter().flatten().map(|elem| *elem.expr))
+                .collect();
+            get_var_decl(
+                name,
+                get_method_call_expr(get_ident_expr("Object"), "assign", args),
+            )
+        })
+        .collect()
+}
+
+/// Transform the elements that were generated for each name into a [VarDecl](VarDecl), which nests them by directory
+/// when `group_depth` is set, and keys them by name when `keyed` is set.
+fn to_grouped_var_decls(config: &Config, matches: &[ImportPaths], map: ElementMap) -> Vec<VarDecl> {
+    if config.group_depth == 0 && !config.keyed {
+        return to_var_decls(map);
+    }
+
+    map.into_iter()
+        .map(|(name, elems)| get_var_decl(name, get_grouped_expr(config, matches, elems)))
+        .collect()
+}
+
+/// Expand a glob `pattern`, relative to the file being transformed (or starting with one of the configured `paths`
+/// aliases), and give back its matches in the configured order, along with the directories that were globbed over (as
+/// context dependencies).
+///
+/// Like `tsc`, an alias with several targets resolves to the first target that has any matches, unless
+/// `merge_path_targets` is set, in which case the matches of every target are merged.
+///
+/// With `roots`, a relative pattern is expanded in every root (see
+/// [resolve_root_patterns](ImportGlobArrayPlugin::resolve_root_patterns)), and the matches of every root are merged.
+#[instrument(level = "info", skip_all, fields(pattern = %pattern))]
+pub(crate) fn get_matches<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    pattern: &str,
+) -> Option<(Vec<ImportPaths>, Vec<String>)> {
+    let alias_candidates = plugin.resolve_path_alias(config, pattern);
+    let is_package = alias_candidates.is_none() && is_package_pattern(pattern);
+    let root_candidates = match alias_candidates {
+        Some(_) => None,
+        None => plugin.resolve_root_patterns(config, pattern),
+    };
+    let merge_candidates = config.merge_path_targets || root_candidates.is_some();
+    let candidates = alias_candidates.or(root_candidates).unwrap_or_else(|| {
+        if is_package {
+            vec![plugin.resolve_package_pattern(pattern)]
+        } else {
+            vec![pattern.to_owned()]
+        }
+    });
+    let mut context_dependencies: Vec<String> = vec![];
+    let mut matches: Vec<ImportPaths> = vec![];
+
+    for candidate in candidates {
+        let (candidate_matches, context_dependency) = glob_candidate(plugin, config, &candidate)?;
+        context_dependencies.push(context_dependency);
+
+        candidate_matches.into_iter().for_each(|import_paths| {
+            if !matches
+                .iter()
+                .any(|existing| existing.absolute_path == import_paths.absolute_path)
+            {
+                matches.push(import_paths);
+            }
+        });
+
+        if !matches.is_empty() && !merge_candidates {
+            break;
+        }
+    }
+
+    if is_package {
+        let node_modules = plugin.node_modules();
+        matches.retain_mut(|import_paths| {
+            let specifier =
+                get_package_specifier(&node_modules, &import_paths.file_path, &config.conditions);
+            specifier
+                .map(|specifier| import_paths.imported_path = specifier)
+                .is_some()
+        });
+    }
+
+    warn_case_collisions(&matches);
+    sort_matches(config, &mut matches);
+    take_matches(config, &mut matches);
+    plugin.record_manifest_entry(pattern, &matches);
+    plugin.match_counts.borrow_mut().push(matches.len());
+    Some((matches, context_dependencies))
+}
+
+/// Expand a single glob pattern, relative to the file being transformed, and give back its matches along with the
+/// directory that was globbed over.
+///
+/// A pattern with a trailing slash, such as `./packages/*/`, matches package directories instead of files, and each
+/// one is imported through its entry point (see [get_package_entry](get_package_entry)), while its path relative to
+/// the glob's base directory stays the directory itself, e.g., `markdown` for `./packages/markdown/dist/index.js`.
+fn glob_candidate<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    pattern: &str,
+) -> Option<(Vec<ImportPaths>, String)> {
+    let is_package_dir_pattern = pattern.ends_with('/');
+    let glob_path =
+        get_normalized_path(&plugin.importer_dir().join(pattern.trim_start_matches('/')));
+    let glob_path = glob_path.to_str()?;
+    let base = glob_base(Path::new(glob_path));
+    plugin.record_watched_dirs(Path::new(glob_path), &base);
+    let context_dependency = plugin.host_path(&base).to_str()?.to_owned();
+
+    let filter = config
+        .filter
+        .as_deref()
+        .and_then(|filter| PathFilter::new(filter).ok());
+    let content_filter = config
+        .content_filter
+        .as_deref()
+        .and_then(|filter| PathFilter::new(filter).ok());
+
+    let paths = match plugin.glob_paths(glob_path, &base) {
+        Ok(paths) => paths,
+        Err(err) => {
+            HANDLER.with(|handler| {
+                handler.err(&format!(
+                    "Import Glob Array Plugin received an invalid pattern `{pattern}`: {err}"
+                ))
+            });
+            return None;
+        }
+    };
+    let mut matches: Vec<ImportPaths> = paths
+        .into_iter()
+        .filter_map(|file_path| {
+            if !is_package_dir_pattern {
+                return plugin.get_paths(config, &file_path, &base);
             }
-            Err(_) => None,
+            let entry = get_package_entry(&file_path, &config.conditions)?;
+            Some(ImportPaths {
+                relative_to_glob: PathBuf::from(to_nfc(
+                    file_path.strip_prefix(&base).ok()?.to_str()?,
+                )),
+                ..plugin.get_paths(config, &entry, &base)?
+            })
+        })
+        .filter(|import_paths| {
+            !config.default_excludes || !is_junk_path(&import_paths.relative_to_glob)
+        })
+        .filter(|import_paths| {
+            !config.exclude_tests || !is_test_path(&import_paths.relative_to_glob)
+        })
+        .filter(|import_paths| {
+            filter
+                .as_ref()
+                .map_or(true, |filter| filter.keep(&import_paths.relative_to_glob))
+        })
+        .filter(|import_paths| {
+            content_filter
+                .as_ref()
+                .map_or(true, |filter| filter.keep_contents(&import_paths.file_path))
         })
-        .filter(|i| i.is_some())
-        .map(|i| i.unwrap())
         .collect();
+    // Sort by the normalized paths, which is the order `glob` gives back for names that are already normalized.
+    matches.sort_by(|a, b| a.relative_to_glob.cmp(&b.relative_to_glob));
+    if !is_package_dir_pattern && Path::new(glob_path).extension().is_none() {
+        matches = probe_extensions(config, matches);
+    }
+    Some((matches, context_dependency))
+}
 
-    Some(TransformedStatements {
-        imports: import_statements,
-        meta: to_var_decls(import_meta_map),
-        names: to_var_decls(name_placeholder_map),
+/// Get a [FnDecl](FnDecl) for each name that looks up the element of a match by its imported path, i.e., `function
+/// resolvePages(path) { switch (path) { case "./pages/home.js": return _iga1; ... } }`. Paths that didn't match give
+/// back `undefined`.
+fn get_lookup_fns(matches: &[ImportPaths], name_placeholder_map: &ElementMap) -> Vec<FnDecl> {
+    name_placeholder_map
+        .iter()
+        .filter_map(|(name, elems)| {
+            let Pat::Ident(binding) = name else {
+                return None;
+            };
+            let cases = matches
+                .iter()
+                .zip(elems)
+                .filter_map(|(import_paths, elem)| {
+                    Some(SwitchCase {
+                        cons: vec![Stmt::Return(ReturnStmt {
+                            arg: Some(elem.as_ref()?.expr.to_owned()),
+                            span: DUMMY_SP,
+                        })],
+                        span: DUMMY_SP,
+                        test: Some(Box::new(get_str_expr(
+                            import_paths.imported_path.to_owned(),
+                        ))),
+                    })
+                })
+                .collect();
+
+            Some(FnDecl {
+                declare: false,
+                function: Box::new(Function {
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Switch(SwitchStmt {
+                            cases,
+                            discriminant: Box::new(get_ident_expr("path")),
+                            span: DUMMY_SP,
+                        })],
+                    }),
+                    decorators: vec![],
+                    is_async: false,
+                    is_generator: false,
+                    params: vec![Param {
+                        decorators: vec![],
+                        pat: get_ident_pat("path"),
+                        span: DUMMY_SP,
+                    }],
+                    return_type: None,
+                    span: DUMMY_SP,
+                    type_params: None,
+                }),
+                ident: Ident::new(
+                    format!("resolve{}", to_pascal_case(&binding.id.sym)).into(),
+                    DUMMY_SP,
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Get a [VarDecl](VarDecl) that exports the binding of each match under a PascalCase name that is derived from its
+/// file name (or, for `index` files, its directory name), so the import doubles as a barrel. The binding of the first
+/// specifier is exported, and matches whose name is already taken are reported and skipped.
+fn get_binding_exports(matches: &[ImportPaths], name_placeholder_map: &ElementMap) -> Vec<VarDecl> {
+    let Some((_, elems)) = name_placeholder_map.first() else {
+        return vec![];
+    };
+    let mut exported_names: Vec<String> = vec![];
+
+    matches
+        .iter()
+        .zip(elems)
+        .filter_map(|(import_paths, elem)| {
+            let path = import_paths.relative_to_glob.with_extension("");
+            let name = match path.file_name().and_then(OsStr::to_str) {
+                Some("index") => path.parent()?.file_name()?.to_str()?,
+                name => name?,
+            };
+            let exported_name = to_pascal_case(name);
+
+            if exported_names.contains(&exported_name) {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "{} can't be exported as {exported_name}, which is already exported by another match",
+                        import_paths.absolute_path
+                    ))
+                });
+                return None;
+            }
+            exported_names.push(exported_name.to_owned());
+
+            Some(get_var_decl(
+                get_ident_pat(&exported_name),
+                *elem.as_ref()?.expr.to_owned(),
+            ))
+        })
+        .collect()
+}
+
+/// Get a [VarDecl](VarDecl) for each of the lazily-loaded bindings in `locals` that contains the array of matched
+/// specifiers, so that applications can warm up the chunks they're likely to need. Nothing is returned unless
+/// [Prefetch::Manifest](Prefetch::Manifest) is configured.
+fn get_prefetch_manifests(
+    config: &Config,
+    matches: &[ImportPaths],
+    locals: &[String],
+) -> Vec<VarDecl> {
+    if config.prefetch != Prefetch::Manifest {
+        return vec![];
+    }
+
+    locals
+        .iter()
+        .map(|local| {
+            get_var_decl(
+                get_ident_pat(&format!("{local}Specifiers")),
+                Expr::Array(ArrayLit {
+                    elems: matches
+                        .iter()
+                        .map(|import_paths| {
+                            Some(ExprOrSpread::from(get_str_expr(
+                                import_paths.imported_path.to_owned(),
+                            )))
+                        })
+                        .collect(),
+                    span: DUMMY_SP,
+                }),
+            )
+        })
+        .collect()
+}
+
+/// Import every match statically under a placeholder name, and collect the placeholders into an array for each of
+/// the original specifiers.
+fn transform_eager<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) -> Vec<ImportDecl> {
+    matches
+        .iter()
+        .map(|import_paths| {
+            get_eager_import_decl(
+                plugin,
+                config,
+                import_paths,
+                &import_specifiers,
+                name_placeholder_map,
+            )
+        })
+        .collect()
+}
+
+/// Get the [ImportDecl](ImportDecl) of a single match, where each of the original specifiers is imported under a
+/// placeholder name that gets pushed onto the specifier's array.
+fn get_eager_import_decl<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    import_paths: &ImportPaths,
+    import_specifiers: &[ImportSpecifier],
+    name_placeholder_map: &mut ElementMap,
+) -> ImportDecl {
+    let specifiers: Vec<SWCImportSpecifier> = import_specifiers
+        .iter()
+        .map(|specifier| {
+            let placeholder = &*plugin.next_id(config, "_iga");
+
+            upsert_map(
+                name_placeholder_map,
+                &specifier.get_local_pat(),
+                ExprOrSpread::from(Box::new(Expr::Ident(Ident::new(
+                    placeholder.into(),
+                    DUMMY_SP,
+                )))),
+            );
+
+            match specifier.as_ref() {
+                SWCImportSpecifier::Default(_) if is_wasm_module(config, import_paths) => {
+                    SWCImportSpecifier::Namespace(ImportStarAsSpecifier {
+                        local: Ident::new(placeholder.into(), DUMMY_SP),
+                        span: DUMMY_SP,
+                    })
+                }
+                SWCImportSpecifier::Default(_) => {
+                    SWCImportSpecifier::Default(ImportDefaultSpecifier {
+                        local: Ident::new(placeholder.into(), DUMMY_SP),
+                        span: DUMMY_SP,
+                    })
+                }
+                SWCImportSpecifier::Named(named) => {
+                    SWCImportSpecifier::Named(ImportNamedSpecifier {
+                        imported: named.imported.clone(),
+                        is_type_only: false,
+                        local: Ident::new(placeholder.into(), DUMMY_SP),
+                        span: DUMMY_SP,
+                    })
+                }
+                SWCImportSpecifier::Namespace(_) => {
+                    SWCImportSpecifier::Namespace(ImportStarAsSpecifier {
+                        local: Ident::new(placeholder.into(), DUMMY_SP),
+                        span: DUMMY_SP,
+                    })
+                }
+            }
+        })
+        .collect();
+
+    ImportDecl {
+        asserts: None,
+        span: DUMMY_SP,
+        specifiers,
+        src: Box::new(Str {
+            raw: None,
+            span: DUMMY_SP,
+            value: import_paths.imported_path.to_owned().into(),
+        }),
+        type_only: false,
+    }
+}
+
+/// Keep a single match for each logical module when the pattern omits extensions, preferring the extension that comes
+/// first in the configured `extensions` (e.g., `foo.ts` over `foo.js`). Matches with any other extension, including
+/// directories, are dropped. Every match is kept when no `extensions` are configured.
+fn probe_extensions(config: &Config, matches: Vec<ImportPaths>) -> Vec<ImportPaths> {
+    if config.extensions.is_empty() {
+        return matches;
+    }
+
+    let priority = |import_paths: &ImportPaths| {
+        let extension = import_paths.file_path.extension()?.to_str()?;
+        config
+            .extensions
+            .iter()
+            .position(|candidate| candidate.trim_start_matches('.') == extension)
+    };
+
+    let mut probed: Vec<(usize, ImportPaths)> = vec![];
+    matches.into_iter().for_each(|import_paths| {
+        let Some(rank) = priority(&import_paths) else {
+            return;
+        };
+        let module = import_paths.file_path.with_extension("");
+
+        match probed
+            .iter_mut()
+            .find(|(_, probed)| probed.file_path.with_extension("") == module)
+        {
+            Some(existing) if rank < existing.0 => *existing = (rank, import_paths),
+            Some(_) => {}
+            None => probed.push((rank, import_paths)),
+        }
+    });
+
+    probed
+        .into_iter()
+        .map(|(_, import_paths)| import_paths)
+        .collect()
+}
+
+/// Warn about matches whose paths only differ by case, e.g., `Button.tsx` and `button.tsx`, since they can't both
+/// exist on a case-insensitive file system, so the build behaves differently depending on where it runs.
+fn warn_case_collisions(matches: &[ImportPaths]) {
+    let mut seen: HashMap<String, &ImportPaths> = HashMap::new();
+
+    matches.iter().for_each(|import_paths| {
+        match seen.entry(import_paths.absolute_path.to_lowercase()) {
+            Entry::Occupied(entry) => HANDLER.with(|handler| {
+                handler.warn(&format!(
+                    "{} and {} only differ by case, so they collide on case-insensitive file systems",
+                    entry.get().absolute_path,
+                    import_paths.absolute_path
+                ))
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(import_paths);
+            }
+        }
+    });
+}
+
+/// Sort matches in place according to the configured [Sort](Sort) and [SortOrder](SortOrder). Globs are already
+/// expanded in path order, so that order is kept as-is unless another one is requested.
+fn sort_matches(config: &Config, matches: &mut [ImportPaths]) {
+    match &config.sort {
+        Sort::Path => {}
+        Sort::Natural => matches.sort_by(|a, b| natural_cmp(&a.imported_path, &b.imported_path)),
+        Sort::Mtime => matches.sort_by_cached_key(|import_paths| {
+            fs::metadata(&import_paths.file_path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        }),
+        Sort::Frontmatter(field) => {
+            matches.sort_by_cached_key(|import_paths| FrontmatterKey::new(import_paths, field))
+        }
+    }
+
+    if config.sort_order == SortOrder::Desc {
+        matches.reverse();
+    }
+}
+
+/// Keep the window of sorted matches that `offset` and `limit` select, so the rest are never imported.
+fn take_matches(config: &Config, matches: &mut Vec<ImportPaths>) {
+    matches.drain(..config.offset.min(matches.len()));
+    if let Some(limit) = config.limit {
+        matches.truncate(limit);
+    }
+}
+
+/// Get an [Expr](Expr) for a `.catch()` handler that re-throws the error of a failed import, wrapped in an error that
+/// names the match and keeps the original error as its `cause`, i.e., `(cause) => { throw Object.assign(new
+/// Error(...), { importedPath }); }`.
+fn get_error_wrapper_expr(imported_path: &str) -> Expr {
+    let error_expr = get_new_expr(
+        "Error",
+        vec![
+            get_str_expr(format!("Failed to import {imported_path}")),
+            get_object_expr(vec![("cause", get_ident_expr("cause"))]),
+        ],
+    );
+    let throw_stmt = Stmt::Throw(ThrowStmt {
+        arg: Box::new(get_method_call_expr(
+            get_ident_expr("Object"),
+            "assign",
+            vec![
+                error_expr,
+                get_object_expr(vec![(
+                    "importedPath",
+                    get_str_expr(imported_path.to_owned()),
+                )]),
+            ],
+        )),
+        span: DUMMY_SP,
+    });
+
+    Expr::Arrow(ArrowExpr {
+        body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![throw_stmt],
+        })),
+        is_async: false,
+        is_generator: false,
+        params: vec![get_ident_pat("cause")],
+        return_type: None,
+        span: DUMMY_SP,
+        type_params: None,
     })
 }
+
+/// Get an [Expr](Expr) that imports a match dynamically, which resolves to the export that `specifier` binds for
+/// default and named specifiers, or to the whole module for namespace specifiers (and default specifiers of
+/// WebAssembly modules).
+fn get_lazy_import_expr<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    specifier: &ImportSpecifier,
+    import_paths: &ImportPaths,
+) -> Expr {
+    let import_expr = plugin.get_dynamic_import_expr(config, import_paths);
+    let Some(export_name) = specifier
+        .get_export_name()
+        .filter(|name| name != "default" || !is_wasm_module(config, import_paths))
+    else {
+        return import_expr;
+    };
+
+    let pick_expr = if config.runtime_helpers {
+        plugin.require_named_import("pick", RUNTIME_PICK_NAME, RUNTIME_SPECIFIER);
+        get_call_expr(RUNTIME_PICK_NAME, vec![get_str_expr(export_name)])
+    } else {
+        get_arrow_expr(
+            vec!["m"],
+            get_member_expr(get_ident_expr("m"), &export_name),
+        )
+    };
+    get_method_call_expr(import_expr, "then", vec![pick_expr])
+}
+
+/// Build a thunk for every match that imports it dynamically, and collect the thunks into an array for each of the
+/// original specifiers. Default and named specifiers resolve to the matching export, while namespace specifiers
+/// resolve to the whole module. With `wrap_lazy_errors`, failed imports are re-thrown with the match's specifier.
+fn transform_lazy<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) {
+    import_specifiers.iter().for_each(|specifier| {
+        matches.iter().for_each(|import_paths| {
+            let mut thunk_body = get_lazy_import_expr(plugin, config, specifier, import_paths);
+            if config.wrap_lazy_errors {
+                let error_wrapper = if config.runtime_helpers {
+                    plugin.require_named_import(
+                        "wrapImportError",
+                        RUNTIME_WRAP_IMPORT_ERROR_NAME,
+                        RUNTIME_SPECIFIER,
+                    );
+                    get_call_expr(
+                        RUNTIME_WRAP_IMPORT_ERROR_NAME,
+                        vec![get_str_expr(import_paths.imported_path.to_owned())],
+                    )
+                } else {
+                    get_error_wrapper_expr(&import_paths.imported_path)
+                };
+                thunk_body = get_method_call_expr(thunk_body, "catch", vec![error_wrapper]);
+            }
+
+            upsert_map(
+                name_placeholder_map,
+                &specifier.get_local_pat(),
+                ExprOrSpread::from(Box::new(get_arrow_expr(vec![], thunk_body))),
+            )
+        })
+    });
+}
+
+/// Import every match dynamically, and collect the imports into an array for each of the original specifiers, which
+/// [await_var_decls](await_var_decls) turns into a single awaited `Promise.all()`.
+fn transform_lazy_eager<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) {
+    import_specifiers.iter().for_each(|specifier| {
+        matches.iter().for_each(|import_paths| {
+            upsert_map(
+                name_placeholder_map,
+                &specifier.get_local_pat(),
+                ExprOrSpread::from(Box::new(get_lazy_import_expr(
+                    plugin,
+                    config,
+                    specifier,
+                    import_paths,
+                ))),
+            )
+        })
+    });
+}
+
+/// Inline the contents of every match, and collect them into an array for each of the original specifiers. JSON files
+/// (and, when `convert_data` is set, YAML and TOML files) are inlined as literals, where named specifiers read the
+/// property of the same name, while other files are inlined as strings. Data files that fail to parse are reported.
+///
+/// Matches that can't be inlined, because they're larger than `max_inline_size` or a named specifier was requested
+/// from a file that isn't JSON, are imported statically instead, unless [InlineOverflow::Error](InlineOverflow::Error)
+/// is configured for files that are too large. Files that are too large in that case, or that can't be read or
+/// parsed, are reported and stand in as `undefined`, so every element stays at the index of its match.
+fn transform_raw<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) -> Vec<ImportDecl> {
+    let has_named = import_specifiers
+        .iter()
+        .any(|specifier| matches!(specifier.as_ref(), SWCImportSpecifier::Named(_)));
+
+    matches
+        .iter()
+        .filter_map(|import_paths| {
+            let file_path = &import_paths.file_path;
+            let size = match fs::metadata(file_path) {
+                Ok(metadata) => metadata.len(),
+                Err(err) => {
+                    skip_raw_match(
+                        &format!("{} couldn't be read: {err}", import_paths.absolute_path),
+                        &import_specifiers,
+                        name_placeholder_map,
+                    );
+                    return None;
+                }
+            };
+
+            if config.max_inline_size.map_or(false, |max| size > max) {
+                if config.inline_overflow == InlineOverflow::Error {
+                    skip_raw_match(
+                        &format!(
+                            "{} is {size} bytes, which is larger than the maxInlineSize of {} bytes",
+                            import_paths.absolute_path,
+                            config.max_inline_size.unwrap_or_default(),
+                        ),
+                        &import_specifiers,
+                        name_placeholder_map,
+                    );
+                    return None;
+                }
+
+                return Some(get_eager_import_decl(
+                    plugin,
+                    config,
+                    import_paths,
+                    &import_specifiers,
+                    name_placeholder_map,
+                ));
+            }
+
+            let contents = match fs::read_to_string(file_path) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    skip_raw_match(
+                        &format!("{} couldn't be read: {err}", import_paths.absolute_path),
+                        &import_specifiers,
+                        name_placeholder_map,
+                    );
+                    return None;
+                }
+            };
+            let json = match file_path.extension().and_then(OsStr::to_str) {
+                Some("json") => serde_json::from_str::<Value>(&contents).ok(),
+                Some(extension) if config.convert_data && is_data_file(import_paths) => {
+                    match parse_data_file(extension, &contents) {
+                        Ok(value) => Some(value),
+                        Err(err) => {
+                            skip_raw_match(
+                                &format!("failed to parse {}: {err}", import_paths.absolute_path),
+                                &import_specifiers,
+                                name_placeholder_map,
+                            );
+                            return None;
+                        }
+                    }
+                }
+                _ => None,
+            };
+
+            if has_named && json.is_none() {
+                return Some(get_eager_import_decl(
+                    plugin,
+                    config,
+                    import_paths,
+                    &import_specifiers,
+                    name_placeholder_map,
+                ));
+            }
+
+            import_specifiers.iter().for_each(|specifier| {
+                let element = match (&json, specifier.get_imported_name()) {
+                    (Some(json), Some(imported_name)) => json
+                        .get(&imported_name)
+                        .map_or_else(|| get_ident_expr("undefined"), get_json_expr),
+                    (Some(json), None) => get_json_expr(json),
+                    (None, _) => get_str_expr(contents.to_owned()),
+                };
+
+                upsert_map(
+                    name_placeholder_map,
+                    &specifier.get_local_pat(),
+                    ExprOrSpread::from(Box::new(element)),
+                )
+            });
+            None
+        })
+        .collect()
+}
+
+/// Report a match that can't be inlined, and give each of the `import_specifiers` an `undefined` element in its place,
+/// so the elements of every binding keep lining up with the matches.
+fn skip_raw_match(
+    message: &str,
+    import_specifiers: &[ImportSpecifier],
+    name_placeholder_map: &mut ElementMap,
+) {
+    HANDLER.with(|handler| handler.err(message));
+    import_specifiers.iter().for_each(|specifier| {
+        upsert_map(
+            name_placeholder_map,
+            &specifier.get_local_pat(),
+            ExprOrSpread::from(Box::new(get_ident_expr("undefined"))),
+        )
+    });
+}
+
+/// Parse the `contents` of a YAML or TOML file, whose format is given by its `extension`, into the JSON value it's
+/// equivalent to.
+fn parse_data_file(extension: &str, contents: &str) -> Result<Value, String> {
+    if extension == "toml" {
+        toml::from_str::<Value>(contents).map_err(|err| err.to_string())
+    } else {
+        serde_yaml::from_str::<Value>(contents).map_err(|err| err.to_string())
+    }
+}
+
+/// Require every match under a placeholder name, and collect the placeholders (or their named exports) into an array
+/// for each of the original specifiers. Default and namespace specifiers both receive `module.exports`, matching how
+/// Node.js exposes CommonJS modules to `import` declarations.
+fn transform_require<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) -> Vec<VarDecl> {
+    let callee = if config.import_style == ImportStyle::CreateRequire {
+        *plugin.needs_create_require.borrow_mut() = true;
+        CREATE_REQUIRE_NAME
+    } else {
+        "require"
+    };
+
+    matches
+        .iter()
+        .map(|import_paths| {
+            let placeholder = &*plugin.next_id(config, "_iga");
+
+            import_specifiers.iter().for_each(|specifier| {
+                let element = match specifier.get_imported_name() {
+                    Some(imported_name) => {
+                        get_member_expr(get_ident_expr(placeholder), &imported_name)
+                    }
+                    None => get_ident_expr(placeholder),
+                };
+
+                upsert_map(
+                    name_placeholder_map,
+                    &specifier.get_local_pat(),
+                    ExprOrSpread::from(Box::new(element)),
+                )
+            });
+
+            get_var_decl(
+                get_ident_pat(placeholder),
+                get_call_expr(
+                    callee,
+                    vec![get_str_expr(import_paths.imported_path.to_owned())],
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Build a React Router route object for every match, which loads its module lazily. The default (or namespace)
+/// specifier receives the array of routes, while named `loader` and `action` specifiers are passed through from each
+/// route module.
+fn transform_react_router_lazy<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) {
+    let exports = RouteExports::from_specifiers(&import_specifiers);
+
+    import_specifiers
+        .iter()
+        .filter(|specifier| !matches!(specifier.as_ref(), SWCImportSpecifier::Named(_)))
+        .for_each(|specifier| {
+            let namespace = matches!(specifier.as_ref(), SWCImportSpecifier::Namespace(_));
+
+            matches.iter().for_each(|import_paths| {
+                upsert_map(
+                    name_placeholder_map,
+                    &specifier.get_local_pat(),
+                    ExprOrSpread::from(Box::new(get_route_expr(
+                        import_paths,
+                        plugin.get_dynamic_import_expr(config, import_paths),
+                        &exports,
+                        namespace,
+                    ))),
+                )
+            })
+        });
+}
+
+/// Build the URL of every match, and collect the URLs into an array for each of the original specifiers. URLs are
+/// resolved against `import.meta.url`, unless a `public_path` is configured, which the match's path relative to the
+/// project root is appended to instead.
+fn transform_url<C: Comments>(
+    plugin: &ImportGlobArrayPlugin<C>,
+    config: &Config,
+    matches: &[ImportPaths],
+    import_specifiers: Vec<ImportSpecifier>,
+    name_placeholder_map: &mut ElementMap,
+) {
+    import_specifiers.iter().for_each(|specifier| {
+        matches.iter().for_each(|import_paths| {
+            let project_path = Path::new(&import_paths.absolute_path)
+                .strip_prefix(&plugin.cwd)
+                .ok()
+                .and_then(Path::to_str);
+
+            let url = match (&config.public_path, project_path) {
+                (Some(public_path), Some(project_path)) => get_str_expr(format!(
+                    "{}/{project_path}",
+                    public_path.trim_end_matches('/')
+                )),
+                _ => get_module_url_expr(&import_paths.imported_path),
+            };
+
+            upsert_map(
+                name_placeholder_map,
+                &specifier.get_local_pat(),
+                ExprOrSpread::from(Box::new(url)),
+            )
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::warn_case_collisions;
+    use crate::test_utils::capture_diagnostics;
+    use crate::ImportPaths;
+
+    fn import_paths(name: &str) -> ImportPaths {
+        ImportPaths {
+            absolute_path: format!("/project/components/{name}"),
+            file_path: PathBuf::from(format!("/cwd/components/{name}")),
+            imported_path: format!("./components/{name}"),
+            relative_to_glob: PathBuf::from(name),
+        }
+    }
+
+    #[test]
+    fn warns_about_matches_that_only_differ_by_case() {
+        let matches = [
+            import_paths("Button.tsx"),
+            import_paths("Card.tsx"),
+            import_paths("button.tsx"),
+        ];
+        let ((), messages) = capture_diagnostics(|| warn_case_collisions(&matches));
+
+        assert_eq!(
+            messages,
+            ["/project/components/Button.tsx and /project/components/button.tsx only differ by case, so they collide \
+              on case-insensitive file systems"]
+        );
+    }
+
+    #[test]
+    fn does_not_warn_about_distinct_matches() {
+        let matches = [import_paths("Button.tsx"), import_paths("Card.tsx")];
+        let ((), messages) = capture_diagnostics(|| warn_case_collisions(&matches));
+
+        assert!(messages.is_empty());
+    }
+}
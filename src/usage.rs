@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use swc_core::ecma::ast::{Ident, ImportDecl, Module};
+use swc_core::ecma::visit::{Visit, VisitWith};
+
+/// Collects the name of every identifier outside of import declarations. Names aren't resolved to their bindings, so
+/// a shadowing variable or a property of the same name also counts as a reference, which errs on the side of keeping
+/// a binding.
+#[derive(Default)]
+struct ReferenceCollector {
+    names: HashSet<String>,
+}
+
+impl Visit for ReferenceCollector {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.names.insert(ident.sym.to_string());
+    }
+
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+}
+
+/// Get the names that are referenced anywhere in `module`, except by the import declarations that bind them.
+pub(crate) fn get_referenced_names(module: &Module) -> HashSet<String> {
+    let mut collector = ReferenceCollector::default();
+    module.visit_with(&mut collector);
+    collector.names
+}
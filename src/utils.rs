@@ -1,78 +1,863 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Component, Path, PathBuf};
+use std::str::Chars;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use swc_core::common::DUMMY_SP;
+use is_glob::is_glob;
+use serde_json::{Map, Value};
+use swc_core::common::comments::{Comment, CommentKind, Comments};
+use swc_core::common::{BytePos, Span, DUMMY_SP};
 use swc_core::ecma::ast::{
-    ArrayLit, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ObjectLit, Pat, Prop, PropName,
-    PropOrSpread, Str, VarDecl, VarDeclKind, VarDeclarator,
+    ArrayLit, ArrowExpr, AwaitExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, Bool, CallExpr,
+    Callee, Decl, Expr, ExprOrSpread, ExprStmt, Ident, Import, ImportDecl, ImportNamedSpecifier,
+    ImportSpecifier, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr, MetaPropKind,
+    ModuleDecl, ModuleExportName, ModuleItem, NewExpr, Null, Number, ObjectLit, ParenExpr, Pat,
+    Prop, PropName, PropOrSpread, Stmt, Str, TsKeywordType, TsKeywordTypeKind, TsLit, TsLitType,
+    TsType, TsTypeAliasDecl, TsUnionOrIntersectionType, TsUnionType, VarDecl, VarDeclKind,
+    VarDeclarator,
 };
+use swc_core::ecma::parser::{Parser, StringInput, Syntax, TsConfig};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use unicode_normalization::UnicodeNormalization;
 
 use crate::ImportPaths;
 
+/// Local name of Node.js' `createRequire`, which is imported from `module`.
+pub(crate) const CREATE_REQUIRE_IMPORT_NAME: &str = "_igaCreateRequire";
+
+/// Name of the `require` function that is created with `createRequire(import.meta.url)`.
+pub(crate) const CREATE_REQUIRE_NAME: &str = "_igaRequire";
+
+/// Specifier of the runtime helpers that ship with the plugin, which generated code calls instead of inlining the same
+/// boilerplate for every match when `runtime_helpers` is set.
+pub(crate) const RUNTIME_SPECIFIER: &str = "swc-import-glob-array-plugin/runtime.mjs";
+
+/// Words that can't be used as the name of a binding in a module, which is always strict mode code.
+const RESERVED_WORDS: [&str; 48] = [
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Attach a comment of the given `kind` in front of a [ModuleItem](ModuleItem). Generated items don't have a position
+/// of their own, so the item is given a fresh span that comments can be attached to before adding the comment.
+pub(crate) fn add_leading_comment<C: Comments>(
+    comments: &Option<C>,
+    item: &mut ModuleItem,
+    kind: CommentKind,
+    text: String,
+) {
+    let span = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => &mut import.span,
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => &mut var.span,
+        ModuleItem::Stmt(Stmt::Expr(expr)) => &mut expr.span,
+        _ => return,
+    };
+
+    add_span_comment(comments, span, kind, text);
+}
+
+/// Attach a comment of the given `kind` in front of whatever node owns `span`, giving it a fresh span first if it
+/// doesn't have a position of its own.
+pub(crate) fn add_span_comment<C: Comments>(
+    comments: &Option<C>,
+    span: &mut Span,
+    kind: CommentKind,
+    text: String,
+) {
+    if let Some(comments) = comments {
+        if *span == DUMMY_SP {
+            *span = Span::dummy_with_cmt();
+        }
+
+        comments.add_leading(
+            span.lo,
+            Comment {
+                kind,
+                span: DUMMY_SP,
+                text: text.into(),
+            },
+        );
+    }
+}
+
+/// Collapse a specifier that points at an `index` file to its directory, e.g., `./widget/index.ts` becomes
+/// `./widget`. Specifiers whose directory is only `.` or `..` are kept as-is.
+pub(crate) fn collapse_index_path(specifier: String) -> String {
+    match specifier.rsplit_once('/') {
+        Some((dir, file))
+            if !matches!(dir, "" | "." | "..")
+                && Path::new(file).file_stem().and_then(OsStr::to_str) == Some("index") =>
+        {
+            dir.to_owned()
+        }
+        _ => specifier,
+    }
+}
+
+/// Get an [Expr](Expr) that contains an arrow function with a single parameter for each of `params`, returning
+/// `body`. Object literals are wrapped in parentheses so they aren't mistaken for a block.
+pub(crate) fn get_arrow_expr(params: Vec<&str>, body: Expr) -> Expr {
+    let body = match body {
+        Expr::Object(_) => Expr::Paren(ParenExpr {
+            expr: Box::new(body),
+            span: DUMMY_SP,
+        }),
+        _ => body,
+    };
+
+    Expr::Arrow(ArrowExpr {
+        body: Box::new(BlockStmtOrExpr::Expr(Box::new(body))),
+        is_async: false,
+        is_generator: false,
+        params: params.into_iter().map(get_ident_pat).collect(),
+        return_type: None,
+        span: DUMMY_SP,
+        type_params: None,
+    })
+}
+
+/// Get an [Expr](Expr) that immediately invokes an async arrow function whose body is `stmts`, i.e.,
+/// `(async () => { ... })()`, which evaluates to the promise of its completion.
+pub(crate) fn get_async_iife_expr(stmts: Vec<Stmt>) -> Expr {
+    let arrow_expr = Expr::Arrow(ArrowExpr {
+        body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        })),
+        is_async: true,
+        is_generator: false,
+        params: vec![],
+        return_type: None,
+        span: DUMMY_SP,
+        type_params: None,
+    });
+
+    Expr::Call(CallExpr {
+        args: vec![],
+        callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
+            expr: Box::new(arrow_expr),
+            span: DUMMY_SP,
+        }))),
+        span: DUMMY_SP,
+        type_args: None,
+    })
+}
+
+/// Get an [Expr](Expr) that awaits `arg`.
+pub(crate) fn get_await_expr(arg: Expr) -> Expr {
+    Expr::Await(AwaitExpr {
+        arg: Box::new(arg),
+        span: DUMMY_SP,
+    })
+}
+
+/// Get an [Expr](Expr) that calls the function `callee` with `args`.
+pub(crate) fn get_call_expr(callee: &str, args: Vec<Expr>) -> Expr {
+    Expr::Call(CallExpr {
+        args: args.into_iter().map(ExprOrSpread::from).collect(),
+        callee: Callee::Expr(Box::new(get_ident_expr(callee))),
+        span: DUMMY_SP,
+        type_args: None,
+    })
+}
+
+/// Get an [ExprStmt](ExprStmt) that calls `callee` with each of `args` as a string literal argument.
+pub(crate) fn get_call_stmt(callee: &str, args: Vec<String>) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        expr: Box::new(get_call_expr(
+            callee,
+            args.into_iter().map(get_str_expr).collect(),
+        )),
+        span: DUMMY_SP,
+    })
+}
+
+/// Get the [ModuleItem](ModuleItem)s that create the `require` function used by
+/// [CreateRequire](crate::config::ImportStyle::CreateRequire), i.e.:
+///
+/// ```js
+/// import { createRequire as _igaCreateRequire } from "module";
+/// const _igaRequire = _igaCreateRequire(import.meta.url);
+/// ```
+pub(crate) fn get_create_require_items() -> Vec<ModuleItem> {
+    vec![
+        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            asserts: None,
+            span: DUMMY_SP,
+            specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                imported: Some(ModuleExportName::Ident(Ident::new(
+                    "createRequire".into(),
+                    DUMMY_SP,
+                ))),
+                is_type_only: false,
+                local: Ident::new(CREATE_REQUIRE_IMPORT_NAME.into(), DUMMY_SP),
+                span: DUMMY_SP,
+            })],
+            src: Box::new(Str {
+                raw: None,
+                span: DUMMY_SP,
+                value: "module".into(),
+            }),
+            type_only: false,
+        })),
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(get_var_decl(
+            get_ident_pat(CREATE_REQUIRE_NAME),
+            get_call_expr(CREATE_REQUIRE_IMPORT_NAME, vec![get_import_meta_url_expr()]),
+        ))))),
+    ]
+}
+
+/// Get an [Expr](Expr) that contains a dynamic `import()` of `src`. The string literal is given `src_span`, so that
+/// comments attached to it end up within the parentheses.
+pub(crate) fn get_dynamic_import_expr(src: &str, src_span: Span) -> Expr {
+    Expr::Call(CallExpr {
+        args: vec![ExprOrSpread::from(Expr::Lit(Lit::Str(Str {
+            raw: None,
+            span: src_span,
+            value: src.into(),
+        })))],
+        callee: Callee::Import(Import { span: DUMMY_SP }),
+        span: DUMMY_SP,
+        type_args: None,
+    })
+}
+
+/// Get the Levenshtein distance between two strings, i.e., the number of single-character insertions, deletions, or
+/// substitutions it takes to turn one into the other.
+pub(crate) fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    a.chars().enumerate().for_each(|(i, a_char)| {
+        let mut current = vec![i + 1];
+        b.iter().enumerate().for_each(|(j, b_char)| {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        });
+        previous = current;
+    });
+    previous[b.len()]
+}
+
+/// Get an [Expr](Expr) that contains an array of the bindings in `names`, i.e., `[a, b]`, or `[...a, ...b]` when
+/// `spread` is set, so the elements of each binding are flattened into the array.
+pub(crate) fn get_ident_array_expr(names: &[String], spread: bool) -> Expr {
+    Expr::Array(ArrayLit {
+        elems: names
+            .iter()
+            .map(|name| {
+                Some(ExprOrSpread {
+                    expr: Box::new(get_ident_expr(name)),
+                    spread: spread.then_some(DUMMY_SP),
+                })
+            })
+            .collect(),
+        span: DUMMY_SP,
+    })
+}
+
+/// Get an [Expr](Expr) that references the identifier `sym`.
+pub(crate) fn get_ident_expr(sym: &str) -> Expr {
+    Expr::Ident(Ident::new(sym.into(), DUMMY_SP))
+}
+
+/// Get a [Pat](Pat) that binds the identifier `sym`.
+pub(crate) fn get_ident_pat(sym: &str) -> Pat {
+    Pat::Ident(BindingIdent {
+        id: Ident::new(sym.into(), DUMMY_SP),
+        type_ann: None,
+    })
+}
+
 /// Get an [ExprOrSpread](ExprOrSpread) that contains an [ObjectLit](ObjectLit) with
 /// two embedded properties: `absolutePath` and `importedPath`, both of which will get
 /// pulled from `absolute_path` and `imported_path` within [ImportPaths](ImportPaths),
-/// respectively.
-pub(crate) fn get_import_map_expr(import_paths: &ImportPaths) -> ExprOrSpread {
-    ExprOrSpread::from(Expr::Object(ObjectLit {
-        props: vec![
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                key: PropName::Ident(Ident::new("absolutePath".into(), DUMMY_SP)),
-                value: Box::new(Expr::Lit(Lit::Str(Str {
-                    raw: None,
-                    span: DUMMY_SP,
-                    value: import_paths.absolute_path.to_owned().into(),
-                }))),
-            }))),
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                key: PropName::Ident(Ident::new("importedPath".into(), DUMMY_SP)),
-                value: Box::new(Expr::Lit(Lit::Str(Str {
+/// respectively. Any properties of `meta` are appended after them. When `fields` is
+/// given, only the properties it names are kept, and properties named in `excluded` are
+/// always dropped.
+pub(crate) fn get_import_map_expr(
+    import_paths: &ImportPaths,
+    meta: &Map<String, Value>,
+    fields: Option<&[String]>,
+    excluded: &[String],
+) -> ExprOrSpread {
+    let mut props = vec![
+        (
+            "absolutePath",
+            get_str_expr(import_paths.absolute_path.to_owned()),
+        ),
+        (
+            "importedPath",
+            get_str_expr(import_paths.imported_path.to_owned()),
+        ),
+    ];
+    meta.iter()
+        .for_each(|(key, value)| props.push((key.as_str(), get_json_expr(value))));
+    props.retain(|(key, _)| {
+        fields.map_or(true, |fields| fields.iter().any(|field| field == key))
+            && !excluded.iter().any(|field| field == key)
+    });
+
+    ExprOrSpread::from(get_object_expr(props))
+}
+
+/// Get an [Expr](Expr) that reads `import.meta.url`.
+pub(crate) fn get_import_meta_url_expr() -> Expr {
+    get_member_expr(
+        Expr::MetaProp(MetaPropExpr {
+            kind: MetaPropKind::ImportMeta,
+            span: DUMMY_SP,
+        }),
+        "url",
+    )
+}
+
+/// Get an [Expr](Expr) that is equivalent to a JSON value, so JSON files can be inlined as literals.
+pub(crate) fn get_json_expr(value: &Value) -> Expr {
+    match value {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: *value,
+        })),
+        Value::Number(value) => Expr::Lit(Lit::Num(Number {
+            raw: None,
+            span: DUMMY_SP,
+            value: value.as_f64().unwrap_or_default(),
+        })),
+        Value::String(value) => get_str_expr(value.to_owned()),
+        Value::Array(values) => Expr::Array(ArrayLit {
+            elems: values
+                .iter()
+                .map(|value| Some(ExprOrSpread::from(get_json_expr(value))))
+                .collect(),
+            span: DUMMY_SP,
+        }),
+        Value::Object(values) => Expr::Object(ObjectLit {
+            props: values
+                .iter()
+                .map(|(key, value)| {
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: get_prop_name(key),
+                        value: Box::new(get_json_expr(value)),
+                    })))
+                })
+                .collect(),
+            span: DUMMY_SP,
+        }),
+    }
+}
+
+/// Get an [Expr](Expr) that accesses the `prop` property of `obj`.
+pub(crate) fn get_member_expr(obj: Expr, prop: &str) -> Expr {
+    Expr::Member(MemberExpr {
+        obj: Box::new(obj),
+        prop: MemberProp::Ident(Ident::new(prop.into(), DUMMY_SP)),
+        span: DUMMY_SP,
+    })
+}
+
+/// Get an [Expr](Expr) that calls the `method` method of `obj` with `args`.
+pub(crate) fn get_method_call_expr(obj: Expr, method: &str, args: Vec<Expr>) -> Expr {
+    Expr::Call(CallExpr {
+        args: args.into_iter().map(ExprOrSpread::from).collect(),
+        callee: Callee::Expr(Box::new(get_member_expr(obj, method))),
+        span: DUMMY_SP,
+        type_args: None,
+    })
+}
+
+/// Get an [Expr](Expr) that resolves `specifier` against the importing module's URL, i.e.,
+/// `new URL("./images/logo.png", import.meta.url).href`.
+pub(crate) fn get_module_url_expr(specifier: &str) -> Expr {
+    get_member_expr(
+        Expr::New(NewExpr {
+            args: Some(vec![
+                ExprOrSpread::from(get_str_expr(specifier.to_owned())),
+                ExprOrSpread::from(get_import_meta_url_expr()),
+            ]),
+            callee: Box::new(get_ident_expr("URL")),
+            span: DUMMY_SP,
+            type_args: None,
+        }),
+        "href",
+    )
+}
+
+/// Get an [ImportDecl](ImportDecl) that imports `imported` from `source` as `local`, e.g.,
+/// `import { imported as local } from "source";`.
+pub(crate) fn get_named_import_decl(imported: &str, local: &str, source: &str) -> ImportDecl {
+    ImportDecl {
+        asserts: None,
+        span: DUMMY_SP,
+        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+            imported: (imported != local)
+                .then(|| ModuleExportName::Ident(Ident::new(imported.into(), DUMMY_SP))),
+            is_type_only: false,
+            local: Ident::new(local.into(), DUMMY_SP),
+            span: DUMMY_SP,
+        })],
+        src: Box::new(Str {
+            raw: None,
+            span: DUMMY_SP,
+            value: source.into(),
+        }),
+        type_only: false,
+    }
+}
+
+/// Get an [Expr](Expr) that constructs `callee` with `args`, e.g., `new Map(...)`.
+pub(crate) fn get_new_expr(callee: &str, args: Vec<Expr>) -> Expr {
+    Expr::New(NewExpr {
+        args: Some(args.into_iter().map(ExprOrSpread::from).collect()),
+        callee: Box::new(get_ident_expr(callee)),
+        span: DUMMY_SP,
+        type_args: None,
+    })
+}
+
+/// Lexically resolve the `.` and `..` components of `path`, without touching the filesystem.
+pub(crate) fn get_normalized_path(path: &Path) -> PathBuf {
+    path.components()
+        .fold(PathBuf::new(), |mut normalized, component| {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir if normalized.file_name().is_some() => {
+                    normalized.pop();
+                }
+                _ => normalized.push(component),
+            }
+            normalized
+        })
+}
+
+/// Get an [Expr](Expr) that contains an [ObjectLit](ObjectLit) with a property for each key and value in `props`.
+pub(crate) fn get_object_expr(props: Vec<(&str, Expr)>) -> Expr {
+    Expr::Object(ObjectLit {
+        props: props
+            .into_iter()
+            .map(|(key, value)| {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: get_prop_name(key),
+                    value: Box::new(value),
+                })))
+            })
+            .collect(),
+        span: DUMMY_SP,
+    })
+}
+
+/// Get a [PropName](PropName) for an object key, which is only quoted when it isn't a valid identifier.
+pub(crate) fn get_prop_name(key: &str) -> PropName {
+    if is_ident(key) {
+        PropName::Ident(Ident::new(key.into(), DUMMY_SP))
+    } else {
+        PropName::Str(Str {
+            raw: None,
+            span: DUMMY_SP,
+            value: key.into(),
+        })
+    }
+}
+
+/// Resolve every symlink along `path`, one component at a time, since `fs::canonicalize` isn't available to plugins.
+/// The resolved prefix never contains a link, so `..` components can be dropped lexically. Links that can't be read,
+/// and any links past the first 40 (which are likely a cycle), are left as-is.
+pub(crate) fn get_real_path(path: &Path) -> PathBuf {
+    let mut real_path = PathBuf::new();
+    let mut remaining: Vec<PathBuf> = path.iter().rev().map(PathBuf::from).collect();
+    let mut links_followed = 0;
+
+    while let Some(component) = remaining.pop() {
+        match component.components().next() {
+            Some(Component::CurDir) => continue,
+            Some(Component::ParentDir) => {
+                real_path.pop();
+                continue;
+            }
+            _ => real_path.push(component),
+        }
+
+        let is_symlink = fs::symlink_metadata(&real_path)
+            .map_or(false, |metadata| metadata.file_type().is_symlink());
+        if !is_symlink || links_followed == 40 {
+            continue;
+        }
+        let Ok(target) = fs::read_link(&real_path) else {
+            continue;
+        };
+
+        links_followed += 1;
+        real_path.pop();
+        remaining.extend(target.iter().rev().map(PathBuf::from));
+    }
+    real_path
+}
+
+/// Get the path to `to` relative to the directory `from`, e.g., `../docs/hello.md` for `/project/src` and
+/// `/project/docs/hello.md`. Both paths are expected to be absolute and normalized.
+pub(crate) fn get_relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(from, to)| from == to)
+        .count();
+
+    from[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(to[common..].iter().copied())
+        .collect()
+}
+
+/// Get the `file:` URL of an absolute path, which is what `import.meta.resolve()` gives back for it, e.g.,
+/// `file:///app/plugins/my%20plugin.js`. Bytes that aren't allowed in the path of a URL are percent-encoded.
+pub(crate) fn get_file_url(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut url = String::from(if path.starts_with('/') {
+        "file://"
+    } else {
+        "file:///"
+    });
+    path.bytes().for_each(|byte| {
+        if byte.is_ascii_alphanumeric() || b"-._~/:@+".contains(&byte) {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{byte:02X}"));
+        }
+    });
+    url
+}
+
+/// Get an [Expr](Expr) that contains a string literal of `value`.
+pub(crate) fn get_str_expr(value: String) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        raw: None,
+        span: DUMMY_SP,
+        value: value.into(),
+    }))
+}
+
+/// Get a [TsTypeAliasDecl](TsTypeAliasDecl) that declares `name` as the union of a string literal type for each of
+/// `values`, or as `never` when there are none.
+pub(crate) fn get_str_union_type_alias(name: &str, values: Vec<String>) -> TsTypeAliasDecl {
+    let mut types: Vec<Box<TsType>> = values
+        .into_iter()
+        .map(|value| {
+            Box::new(TsType::TsLitType(TsLitType {
+                lit: TsLit::Str(Str {
                     raw: None,
                     span: DUMMY_SP,
-                    value: import_paths.imported_path.to_owned().into(),
-                }))),
-            }))),
-        ],
+                    value: value.into(),
+                }),
+                span: DUMMY_SP,
+            }))
+        })
+        .collect();
+
+    let type_ann = match types.len() {
+        0 => Box::new(TsType::TsKeywordType(TsKeywordType {
+            kind: TsKeywordTypeKind::TsNeverKeyword,
+            span: DUMMY_SP,
+        })),
+        1 => types.remove(0),
+        _ => Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsUnionType(TsUnionType {
+                span: DUMMY_SP,
+                types,
+            }),
+        )),
+    };
+
+    TsTypeAliasDecl {
+        declare: false,
+        id: Ident::new(name.into(), DUMMY_SP),
         span: DUMMY_SP,
-    }))
+        type_ann,
+        type_params: None,
+    }
+}
+
+/// Get a `const` [VarDecl](VarDecl) that declares `name`, initialized to `init`.
+pub(crate) fn get_var_decl(name: Pat, init: Expr) -> VarDecl {
+    VarDecl {
+        declare: false,
+        decls: vec![VarDeclarator {
+            definite: false,
+            init: Some(Box::new(init)),
+            name,
+            span: DUMMY_SP,
+        }],
+        kind: VarDeclKind::Const,
+        span: DUMMY_SP,
+    }
+}
+
+/// Get the static directory prefix of a glob pattern, i.e., every leading path component that doesn't contain a glob
+/// metacharacter. This is the directory that gets walked when the pattern is expanded.
+pub(crate) fn glob_base(pattern: &Path) -> PathBuf {
+    let base: PathBuf = pattern
+        .components()
+        .take_while(|component| !is_glob(&component.as_os_str().to_string_lossy()))
+        .collect();
+
+    if base == pattern {
+        base.parent().map(Path::to_path_buf).unwrap_or(base)
+    } else {
+        base
+    }
+}
+
+/// Check whether `sym` can be the name of a binding, i.e., it's an identifier that isn't a reserved word.
+pub(crate) fn is_binding_ident(sym: &str) -> bool {
+    is_ident(sym) && !RESERVED_WORDS.contains(&sym)
+}
+
+/// Check whether `sym` is a valid identifier, limited to ASCII characters.
+pub(crate) fn is_ident(sym: &str) -> bool {
+    !sym.is_empty()
+        && sym.chars().enumerate().all(|(i, c)| {
+            c == '_' || c == '$' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+        })
+}
+
+/// Compare two strings in natural order, where runs of digits are compared by their numeric value, so that
+/// `page-2` sorts before `page-10`.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a);
+                let y = take_digits(&mut b);
+                let ordering = x
+                    .trim_start_matches('0')
+                    .len()
+                    .cmp(&y.trim_start_matches('0').len())
+                    .then_with(|| x.trim_start_matches('0').cmp(y.trim_start_matches('0')));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+/// Parse a TypeScript type, e.g., `Record<string, () => Promise<unknown>>`, so it can be used as a type annotation.
+/// The spans of the parsed type are dropped, since they don't point into the file being transformed.
+pub(crate) fn parse_ts_type(source: &str) -> Option<Box<TsType>> {
+    let source = format!("let _: {source};");
+    let input = StringInput::new(&source, BytePos(0), BytePos(source.len() as u32));
+    let mut parser = Parser::new(Syntax::Typescript(TsConfig::default()), input, None);
+    let module = parser.parse_module().ok()?;
+    if !parser.take_errors().is_empty() {
+        return None;
+    }
+
+    let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = module.body.into_iter().next()? else {
+        return None;
+    };
+    let Pat::Ident(BindingIdent { type_ann, .. }) = var_decl.decls.into_iter().next()?.name else {
+        return None;
+    };
+
+    let mut ts_type = type_ann?.type_ann;
+    ts_type.visit_mut_with(&mut DropSpans);
+    Some(ts_type)
+}
+
+struct DropSpans;
+
+impl VisitMut for DropSpans {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
 }
 
-/// Transform a map of names and [ExprOrSpread](ExprOrSpread) elements to a vector
-/// (array) of [VarDecl](VarDecl)s.
-pub(crate) fn to_var_decls(map: HashMap<Pat, Vec<Option<ExprOrSpread>>>) -> Vec<VarDecl> {
+/// Turn a name that is derived from a file name (or from config) into the name of a binding, deterministically, by
+/// replacing each character that can't be part of an identifier with `_`, and prefixing an `_` to names that are empty,
+/// start with a digit, or are reserved words, e.g., `_404` for `404` and `_class` for `class`.
+pub(crate) fn to_binding_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c == '_' || c == '$' || c.is_ascii_alphanumeric() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if !is_binding_ident(&ident) {
+        format!("_{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Normalize a string to Unicode Normalization Form C, e.g., `e` followed by a combining acute accent becomes `é`.
+pub(crate) fn to_nfc(value: &str) -> String {
+    value.nfc().collect()
+}
+
+/// Convert a file name to a PascalCase identifier, e.g., `hello-world` becomes `HelloWorld`. Names that would start with
+/// a digit are prefixed with an underscore, like any other name that [to_binding_ident](to_binding_ident) sanitizes.
+pub(crate) fn to_pascal_case(name: &str) -> String {
+    let pascal_case: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    to_binding_ident(&pascal_case)
+}
+
+/// Transform a list of names and their [ExprOrSpread](ExprOrSpread) elements to a vector
+/// (array) of [VarDecl](VarDecl)s, in the order the names were first seen.
+pub(crate) fn to_var_decls(map: Vec<(Pat, Vec<Option<ExprOrSpread>>)>) -> Vec<VarDecl> {
     map.into_iter()
         .map(|item| {
             let (name, elems) = item;
 
-            VarDecl {
-                declare: false,
-                decls: vec![VarDeclarator {
-                    definite: false,
-                    init: Some(Box::new(Expr::Array(ArrayLit {
-                        elems,
-                        span: DUMMY_SP,
-                    }))),
-                    name,
+            get_var_decl(
+                name,
+                Expr::Array(ArrayLit {
+                    elems,
                     span: DUMMY_SP,
-                }],
-                kind: VarDeclKind::Const,
-                span: DUMMY_SP,
-            }
+                }),
+            )
         })
         .collect()
 }
 
-/// Update the inner [Vec](Vec) within a [HashMap](HashMap); however, first check if it has yet to be
-/// initialized, and if that's the case, initialize it first, then push the new value to it.
+/// Read the JSON object at `path`, let `update` change it, then write it back, creating any missing parent directories.
+/// Files that don't exist yet, or that can't be parsed (e.g., because a process was stopped while writing them), are
+/// read as an empty object. The object is written to a temporary file next to `path` first, which is then renamed over
+/// it, so processes that read the file at the same time never see it half written.
+pub(crate) fn update_json_file(
+    path: &Path,
+    update: impl FnOnce(&mut Map<String, Value>),
+) -> Result<(), String> {
+    let mut value = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .and_then(|value| match value {
+            Value::Object(value) => Some(value),
+            _ => None,
+        })
+        .unwrap_or_default();
+    update(&mut value);
+
+    let contents = serde_json::to_string_pretty(&value).map_err(|err| err.to_string())?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+
+    // `std::process::id()` isn't supported under WASI, so the temporary file is told apart from those of other
+    // processes by the time it's written at instead.
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos());
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{file_name}.{nanos}.tmp"));
+    fs::write(&temp_path, contents + "\n")
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&temp_path);
+            format!("{} couldn't be written: {err}", path.display())
+        })
+}
+
+/// Update the inner [Vec](Vec) of a name within a list of names; however, first check if it has yet to be
+/// initialized, and if that's the case, append it to the end of the list, then push the new value to it.
 pub(crate) fn upsert_map(
-    map: &mut HashMap<Pat, Vec<Option<ExprOrSpread>>>,
+    map: &mut Vec<(Pat, Vec<Option<ExprOrSpread>>)>,
     key: &Pat,
     value: ExprOrSpread,
 ) {
-    if !map.contains_key(&key) {
-        map.insert(key.clone(), vec![]);
+    if !map.iter().any(|(name, _)| name == key) {
+        map.push((key.clone(), vec![]));
     }
 
-    if let Some(inner_items) = map.get_mut(&key) {
+    if let Some((_, inner_items)) = map.iter_mut().find(|(name, _)| name == key) {
         inner_items.push(Some(value))
     }
 }
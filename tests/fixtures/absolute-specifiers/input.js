@@ -0,0 +1,3 @@
+// import-glob: specifierStyle=absolute
+import workers from "./workers/*.js";
+import browserWorkers from "./workers/*.js";
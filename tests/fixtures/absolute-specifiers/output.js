@@ -0,0 +1,8 @@
+import _iga1 from "$DIR/tests/fixtures/absolute-specifiers/workers/resize.js";
+const workers = [
+    _iga1
+];
+import _iga2 from "./workers/resize.js";
+const browserWorkers = [
+    _iga2
+];
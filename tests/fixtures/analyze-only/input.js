@@ -0,0 +1,2 @@
+import docs from "./docs/*.md";
+import { marked } from "marked";
@@ -0,0 +1,2 @@
+import { dir as foo } from "./docs/*";
+import wow, { _importMeta as meta, foo as bar } from "./docs/*";
@@ -0,0 +1,26 @@
+import { dir as _iga } from "./docs/hello.mdx";
+import { dir as _iga2 } from "./docs/world.mdx";
+const foo = [
+    _iga,
+    _iga2
+];
+import _iga3, { foo as _iga4 } from "./docs/hello.mdx";
+import _iga5, { foo as _iga6 } from "./docs/world.mdx";
+const wow = [
+    _iga3,
+    _iga5
+];
+const bar = [
+    _iga4,
+    _iga6
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/babel-compat/docs/hello.mdx",
+        importedPath: "./docs/hello.mdx"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/babel-compat/docs/world.mdx",
+        importedPath: "./docs/world.mdx"
+    }
+];
@@ -7,14 +7,14 @@ const foo = [
 ];
 import _iga3, { foo as _iga4 } from "./docs/hello.mdx";
 import _iga5, { foo as _iga6 } from "./docs/world.mdx";
-const bar = [
-    _iga4,
-    _iga6
-];
 const wow = [
     _iga3,
     _iga5
 ];
+const bar = [
+    _iga4,
+    _iga6
+];
 const meta = [
     {
         absolutePath: "$DIR/tests/fixtures/basic/docs/hello.mdx",
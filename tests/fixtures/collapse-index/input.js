@@ -0,0 +1 @@
+import widgets, { _importMeta as meta } from "./widgets/*/index.ts";
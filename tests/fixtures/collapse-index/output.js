@@ -0,0 +1,16 @@
+import _iga1 from "./widgets/button";
+import _iga2 from "./widgets/card";
+const widgets = [
+    _iga1,
+    _iga2
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/collapse-index/widgets/button/index.ts",
+        importedPath: "./widgets/button"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/collapse-index/widgets/card/index.ts",
+        importedPath: "./widgets/card"
+    }
+];
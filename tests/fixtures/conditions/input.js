@@ -0,0 +1 @@
+import icons from "ui-kit/*/icons/*.js";
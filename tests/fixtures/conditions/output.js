@@ -0,0 +1,6 @@
+import _iga1 from "ui-kit/icons/close";
+import _iga2 from "ui-kit/icons/menu";
+const icons = [
+    _iga1,
+    _iga2
+];
@@ -0,0 +1,2 @@
+export const route = "/";
+export default function Home() {}
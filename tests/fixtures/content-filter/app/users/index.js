@@ -0,0 +1,2 @@
+export const route = "/users";
+export default function Users() {}
@@ -0,0 +1 @@
+import routes from "./app/**/*.js";
@@ -0,0 +1,6 @@
+import _iga1 from "./app/home.js";
+import _iga2 from "./app/users/index.js";
+const routes = [
+    _iga1,
+    _iga2
+];
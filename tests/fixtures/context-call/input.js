@@ -0,0 +1 @@
+import docs from "./docs/*.md";
@@ -0,0 +1,2 @@
+import environments from "./config/*.y*ml";
+import { service } from "./services/*.toml";
@@ -0,0 +1,15 @@
+const environments = [
+    {
+        name: "production",
+        replicas: 3
+    },
+    {
+        name: "staging",
+        replicas: 1
+    }
+];
+const service = [
+    {
+        port: 8080
+    }
+];
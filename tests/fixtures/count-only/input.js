@@ -0,0 +1,5 @@
+// import-glob: countOnly
+import { _importMeta as count } from "./posts/*.md";
+// import-glob: countOnly
+import empty from "./drafts/*.md";
+import posts from "./posts/*.md";
@@ -0,0 +1,10 @@
+const count = 3;
+const empty = 0;
+import _iga1 from "./posts/first.md";
+import _iga2 from "./posts/second.md";
+import _iga3 from "./posts/third.md";
+const posts = [
+    _iga1,
+    _iga2,
+    _iga3
+];
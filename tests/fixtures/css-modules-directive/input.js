@@ -0,0 +1,2 @@
+// import-glob: cssModules
+import styles from "./styles/*.module.css";
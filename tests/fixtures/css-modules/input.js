@@ -0,0 +1 @@
+import styles from "./styles/*.module.css";
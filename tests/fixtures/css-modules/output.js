@@ -0,0 +1,6 @@
+import _iga1 from "./styles/Button.module.css";
+import _iga2 from "./styles/Card.module.css";
+const styles = {
+    Button: _iga1,
+    Card: _iga2
+};
@@ -0,0 +1,4 @@
+import _iga1 from "./docs/intro.md";
+var docs = [
+    _iga1
+];
@@ -0,0 +1 @@
+import * as types from "./types/*.ts";
@@ -0,0 +1,4 @@
+import * as _iga1 from "./types/user.ts";
+const types = [
+    _iga1
+];
@@ -0,0 +1,2 @@
+// import-glob: sort=natural, sortOrder=desc
+import pages from "./pages/*.md";
@@ -0,0 +1,8 @@
+import _iga1 from "./pages/page-10.md";
+import _iga2 from "./pages/page-2.md";
+import _iga3 from "./pages/page-1.md";
+const pages = [
+    _iga1,
+    _iga2,
+    _iga3
+];
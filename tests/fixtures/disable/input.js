@@ -0,0 +1,2 @@
+/* import-glob-array-disable */
+import docs from "./docs/*.md";
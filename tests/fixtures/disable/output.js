@@ -0,0 +1 @@
+/* import-glob-array-disable */ import docs from "./docs/*.md";
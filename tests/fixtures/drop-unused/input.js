@@ -0,0 +1,3 @@
+import docs, { _importMeta as meta } from "./docs/*.md";
+import { title as titles } from "./docs/*.md";
+console.log(meta.map(({ importedPath }) => importedPath));
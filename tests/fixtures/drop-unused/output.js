@@ -0,0 +1,7 @@
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/drop-unused/docs/hello.md",
+        importedPath: "./docs/hello.md"
+    }
+];
+console.log(meta.map(({ importedPath }) => importedPath));
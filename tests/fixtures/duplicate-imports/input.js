@@ -0,0 +1,2 @@
+import docs from "./docs/*.md";
+import pages, { _importMeta as meta } from "./docs/*.md";
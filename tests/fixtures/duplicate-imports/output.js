@@ -0,0 +1,11 @@
+import _iga1 from "./docs/intro.md";
+const docs = [
+    _iga1
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/duplicate-imports/docs/intro.md",
+        importedPath: "./docs/intro.md"
+    }
+];
+const pages = docs;
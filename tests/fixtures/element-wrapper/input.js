@@ -0,0 +1 @@
+import routes from "./routes/*.js";
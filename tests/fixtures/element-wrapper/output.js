@@ -0,0 +1,13 @@
+import { defineRoute } from "@app/router";
+import _iga1 from "./routes/about.js";
+import _iga2 from "./routes/home.js";
+const routes = [
+    defineRoute(_iga1, {
+        absolutePath: "$DIR/tests/fixtures/element-wrapper/routes/about.js",
+        importedPath: "./routes/about.js"
+    }),
+    defineRoute(_iga2, {
+        absolutePath: "$DIR/tests/fixtures/element-wrapper/routes/home.js",
+        importedPath: "./routes/home.js"
+    })
+];
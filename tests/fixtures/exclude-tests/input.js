@@ -0,0 +1 @@
+import components from "./components/**/*.js";
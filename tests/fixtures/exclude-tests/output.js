@@ -0,0 +1,4 @@
+import _iga1 from "./components/button.js";
+const components = [
+    _iga1
+];
@@ -0,0 +1,8 @@
+import _iga1 from "./components/card/index.tsx";
+import _iga2 from "./components/date-picker.tsx";
+const components = [
+    _iga1,
+    _iga2
+];
+export const Card = _iga1;
+export const DatePicker = _iga2;
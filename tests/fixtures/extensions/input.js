@@ -0,0 +1 @@
+import commands from "./commands/*";
@@ -0,0 +1,6 @@
+import _iga1 from "./commands/build.ts";
+import _iga2 from "./commands/serve.js";
+const commands = [
+    _iga1,
+    _iga2
+];
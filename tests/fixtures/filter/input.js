@@ -0,0 +1 @@
+import schemas from "./schemas/*.ts";
@@ -0,0 +1,6 @@
+import _iga1 from "./schemas/post.ts";
+import _iga2 from "./schemas/user.ts";
+const schemas = [
+    _iga1,
+    _iga2
+];
@@ -0,0 +1 @@
+import { _importMeta as posts } from "./posts/*.md";
@@ -0,0 +1,14 @@
+const posts = [
+    {
+        absolutePath: "$DIR/tests/fixtures/git-meta/posts/hello.md",
+        importedPath: "./posts/hello.md",
+        lastCommit: "4f2a1c9e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a39",
+        lastModified: 1700000000000
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/git-meta/posts/world.md",
+        importedPath: "./posts/world.md",
+        lastCommit: "0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c",
+        lastModified: 1710000000000
+    }
+];
@@ -0,0 +1 @@
+import posts, { _importMeta as meta } from "./posts/*.md"; // glob-meta: {"collection": "blog", "tags": ["news"]}
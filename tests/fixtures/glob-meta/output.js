@@ -0,0 +1,14 @@
+import _iga1 from "./posts/hello.md";
+const posts = [
+    _iga1
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/glob-meta/posts/hello.md",
+        importedPath: "./posts/hello.md",
+        collection: "blog",
+        tags: [
+            "news"
+        ]
+    }
+];
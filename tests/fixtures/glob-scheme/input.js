@@ -0,0 +1,2 @@
+import docs from "glob:./docs/*.md";
+import route from "./routes/[id].js";
@@ -0,0 +1,5 @@
+import _iga1 from "./docs/intro.md";
+const docs = [
+    _iga1
+];
+import route from "./routes/[id].js";
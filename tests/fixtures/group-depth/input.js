@@ -0,0 +1,2 @@
+// import-glob: groupDepth=2
+import locales from "./locales/*/*/*.json";
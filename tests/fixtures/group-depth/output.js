@@ -0,0 +1,18 @@
+import _iga1 from "./locales/en/common/buttons.json";
+import _iga2 from "./locales/en/errors/http.json";
+import _iga3 from "./locales/fr/common/buttons.json";
+const locales = {
+    en: {
+        common: [
+            _iga1
+        ],
+        errors: [
+            _iga2
+        ]
+    },
+    fr: {
+        common: [
+            _iga3
+        ]
+    }
+};
@@ -0,0 +1 @@
+import images, { _importMeta as meta } from "./images/*";
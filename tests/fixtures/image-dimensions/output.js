@@ -0,0 +1,20 @@
+import _iga1 from "./images/dot.png";
+import _iga2 from "./images/pixel.gif";
+const images = [
+    _iga1,
+    _iga2
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/image-dimensions/images/dot.png",
+        importedPath: "./images/dot.png",
+        width: 3,
+        height: 2
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/image-dimensions/images/pixel.gif",
+        importedPath: "./images/pixel.gif",
+        width: 5,
+        height: 4
+    }
+];
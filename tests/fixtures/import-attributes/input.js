@@ -0,0 +1,2 @@
+import pages from "./pages/*.js" assert { glob: "lazy", sort: "natural" };
+import keyed from "./pages/*.js" assert { keyed: "true" };
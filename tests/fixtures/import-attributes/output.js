@@ -0,0 +1,13 @@
+const pages = [
+    () => import("./pages/page-1.js").then((m) => m.default),
+    () => import("./pages/page-2.js").then((m) => m.default),
+    () => import("./pages/page-10.js").then((m) => m.default)
+];
+import _iga1 from "./pages/page-1.js";
+import _iga2 from "./pages/page-10.js";
+import _iga3 from "./pages/page-2.js";
+const keyed = {
+    "page-1": _iga1,
+    "page-10": _iga2,
+    "page-2": _iga3
+};
@@ -0,0 +1 @@
+export default "page-10";
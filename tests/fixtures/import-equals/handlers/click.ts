@@ -0,0 +1 @@
+export = () => "click";
@@ -0,0 +1 @@
+import handlers = require("./handlers/*.ts");
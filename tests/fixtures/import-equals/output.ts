@@ -0,0 +1,6 @@
+import _iga1 = require("./handlers/click.ts");
+import _iga2 = require("./handlers/submit.ts");
+const handlers = [
+    _iga1,
+    _iga2
+];
@@ -0,0 +1,4 @@
+import _iga1 from "@/docs/intro.md";
+const docs = [
+    _iga1
+];
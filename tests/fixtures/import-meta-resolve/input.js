@@ -0,0 +1,4 @@
+const plugins = import.meta.resolve("./plugins/*.js");
+const workers = plugins.map((plugin)=>new Worker(plugin, {
+        type: "module"
+    }));
@@ -0,0 +1,7 @@
+const plugins = [
+    "file://$DIR/tests/fixtures/import-meta-resolve/plugins/markdown.js",
+    "file://$DIR/tests/fixtures/import-meta-resolve/plugins/toc.js"
+];
+const workers = plugins.map((plugin)=>new Worker(plugin, {
+        type: "module"
+    }));
@@ -0,0 +1,2 @@
+// import-glob: injectEntries=["./default-handler.js"]
+import handlers, { _importMeta as meta } from "./handlers/*.js";
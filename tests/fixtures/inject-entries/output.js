@@ -0,0 +1,22 @@
+import _iga1 from "./handlers/click.js";
+import _iga2 from "./handlers/submit.js";
+import _iga3 from "./default-handler.js";
+const handlers = [
+    _iga1,
+    _iga2,
+    _iga3
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/inject-entries/handlers/click.js",
+        importedPath: "./handlers/click.js"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/inject-entries/handlers/submit.js",
+        importedPath: "./handlers/submit.js"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/inject-entries/default-handler.js",
+        importedPath: "./default-handler.js"
+    }
+];
@@ -0,0 +1 @@
+import { _importMeta as meta } from "./packages/*/index.js";
@@ -0,0 +1,14 @@
+import "./packages/linked/index.js";
+import "./packages/local/index.js";
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/is-symlink/packages/linked/index.js",
+        importedPath: "./packages/linked/index.js",
+        isSymlink: true
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/is-symlink/packages/local/index.js",
+        importedPath: "./packages/local/index.js",
+        isSymlink: false
+    }
+];
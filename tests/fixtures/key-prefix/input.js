@@ -0,0 +1,3 @@
+import commands from "./commands/*.js";
+// import-glob: keyPrefix="", keySuffix=Command
+import handlers from "./commands/*.js";
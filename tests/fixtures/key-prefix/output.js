@@ -0,0 +1,12 @@
+import _iga1 from "./commands/build.js";
+import _iga2 from "./commands/serve.js";
+const commands = {
+    "cmd:build": _iga1,
+    "cmd:serve": _iga2
+};
+import _iga3 from "./commands/build.js";
+import _iga4 from "./commands/serve.js";
+const handlers = {
+    buildCommand: _iga3,
+    serveCommand: _iga4
+};
@@ -0,0 +1 @@
+import widgets from "./widgets/*/index.ts";
@@ -0,0 +1,6 @@
+import _iga1 from "./widgets/button-group/index.ts";
+import _iga2 from "./widgets/card/index.ts";
+const widgets = {
+    ButtonGroup: _iga1,
+    Card: _iga2
+};
@@ -0,0 +1,12 @@
+import _iga1 from "./docs/guides/intro.md";
+import _iga2 from "./docs/index.md";
+const docs = new Map([
+    [
+        "guides/intro",
+        _iga1
+    ],
+    [
+        "index",
+        _iga2
+    ]
+]);
@@ -0,0 +1,3 @@
+import icons from "./icons/*.svg";
+// import-glob: keyCollision=suffix
+import widgets from "./widgets/*/index.ts";
@@ -0,0 +1,12 @@
+import _iga1 from "./icons/close.svg";
+import _iga2 from "./icons/menu.svg";
+const icons = {
+    close: _iga1,
+    menu: _iga2
+};
+import _iga3 from "./widgets/button/index.ts";
+import _iga4 from "./widgets/card/index.ts";
+const widgets = {
+    index: _iga3,
+    "index-2": _iga4
+};
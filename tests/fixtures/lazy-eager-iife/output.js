@@ -0,0 +1,7 @@
+const locales = [];
+export const localesReady = (async () => {
+    locales.push(...await Promise.all([
+        import("./locales/en.json").then((m) => m.default),
+        import("./locales/fr.json").then((m) => m.default)
+    ]));
+})();
@@ -0,0 +1,16 @@
+const _igaMeta1 = [
+    {
+        absolutePath: "$DIR/tests/fixtures/lazy-eager-settled/locales/en.json",
+        importedPath: "./locales/en.json"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/lazy-eager-settled/locales/fr.json",
+        importedPath: "./locales/fr.json"
+    }
+];
+const locales = (await Promise.allSettled([
+    import("./locales/en.json").then((m) => m.default),
+    import("./locales/fr.json").then((m) => m.default)
+])).map((result, i) => Object.assign(result, {
+    meta: _igaMeta1[i]
+}));
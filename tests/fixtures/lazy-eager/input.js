@@ -0,0 +1 @@
+import locales from "./locales/*.json";
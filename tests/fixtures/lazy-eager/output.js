@@ -0,0 +1,4 @@
+const locales = await Promise.all([
+    import("./locales/en.json").then((m) => m.default),
+    import("./locales/fr.json").then((m) => m.default)
+]);
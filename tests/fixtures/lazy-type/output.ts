@@ -0,0 +1,4 @@
+const pages: Record<string, () => Promise<unknown>> = {
+    about: () => import("./pages/about.ts").then((m) => m.default),
+    home: () => import("./pages/home.ts").then((m) => m.default)
+};
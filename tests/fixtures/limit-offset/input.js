@@ -0,0 +1 @@
+import posts from "./posts/post-?.md?limit=2&offset=1";
@@ -0,0 +1,6 @@
+import _iga1 from "./posts/post-2.md";
+import _iga2 from "./posts/post-3.md";
+const posts = [
+    _iga1,
+    _iga2
+];
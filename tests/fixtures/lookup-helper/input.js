@@ -0,0 +1,2 @@
+import pages from "./pages/*.js";
+export const page = resolvePages("./pages/home.js");
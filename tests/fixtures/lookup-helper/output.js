@@ -0,0 +1,15 @@
+import _iga1 from "./pages/about.js";
+import _iga2 from "./pages/home.js";
+const pages = [
+    _iga1,
+    _iga2
+];
+function resolvePages(path) {
+    switch(path){
+        case "./pages/about.js":
+            return _iga1;
+        case "./pages/home.js":
+            return _iga2;
+    }
+}
+export const page = resolvePages("./pages/home.js");
@@ -0,0 +1,2 @@
+import docs from "./docs/*.md";
+import drafts from "./drafts/*.md";
@@ -0,0 +1,7 @@
+/* import-glob-array: 2 globs matched 2 files */ import _iga1 from "./docs/intro.md";
+import _iga2 from "./docs/setup.md";
+const docs = [
+    _iga1,
+    _iga2
+];
+const drafts = [];
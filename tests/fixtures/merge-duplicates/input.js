@@ -0,0 +1,2 @@
+import commands from "./commands/*.js";
+import commands from "./extra/*.js";
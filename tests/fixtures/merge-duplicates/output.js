@@ -0,0 +1,14 @@
+import _iga2 from "./commands/build.js";
+import _iga3 from "./commands/serve.js";
+const _iga1 = [
+    _iga2,
+    _iga3
+];
+import _iga5 from "./extra/audit.js";
+const _iga4 = [
+    _iga5
+];
+const commands = [
+    ..._iga1,
+    ..._iga4
+];
@@ -0,0 +1,5 @@
+// import-glob: mergeInto=commands
+import core from "./commands/*.js";
+import utils from "./utils/*.js";
+// import-glob: mergeInto=commands
+import plugins from "./plugins/*/commands/*.js";
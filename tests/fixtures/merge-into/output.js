@@ -0,0 +1,20 @@
+import _iga1 from "./commands/build.js";
+import _iga2 from "./commands/serve.js";
+const core = [
+    _iga1,
+    _iga2
+];
+import _iga3 from "./utils/format.js";
+const utils = [
+    _iga3
+];
+import _iga4 from "./plugins/deploy/commands/deploy.js";
+import _iga5 from "./plugins/lint/commands/lint.js";
+const plugins = [
+    _iga4,
+    _iga5
+];
+const commands = [
+    ...core,
+    ...plugins
+];
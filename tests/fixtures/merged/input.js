@@ -0,0 +1 @@
+import * as api from "./commands/*.js";
@@ -0,0 +1,3 @@
+import * as _iga1 from "./commands/posts.js";
+import * as _iga2 from "./commands/users.js";
+const api = Object.assign({}, _iga1, _iga2);
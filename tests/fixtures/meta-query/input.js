@@ -0,0 +1 @@
+import docs, { _importMeta as meta } from "./docs/*?meta=importedPath,relativeToGlob";
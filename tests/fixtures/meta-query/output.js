@@ -0,0 +1,16 @@
+import _iga1 from "./docs/hello.mdx";
+import _iga2 from "./docs/world.mdx";
+const docs = [
+    _iga1,
+    _iga2
+];
+const meta = [
+    {
+        importedPath: "./docs/hello.mdx",
+        relativeToGlob: "hello.mdx"
+    },
+    {
+        importedPath: "./docs/world.mdx",
+        relativeToGlob: "world.mdx"
+    }
+];
@@ -0,0 +1,8 @@
+import _iga1 from "./plugins/analytics.cjs";
+import _iga2 from "./plugins/legacy.cjs";
+import _iga3 from "./plugins/search.mjs";
+const plugins = [
+    _iga1,
+    _iga2,
+    _iga3
+];
@@ -0,0 +1 @@
+export * as handlers from "./handlers/*.ts";
@@ -0,0 +1,3 @@
+export * as click from "./handlers/click.ts";
+export * as "key-down" from "./handlers/key-down.ts";
+export * as submit from "./handlers/submit.ts";
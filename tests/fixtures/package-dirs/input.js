@@ -0,0 +1 @@
+import plugins from "./packages/*/";
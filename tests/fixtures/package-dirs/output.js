@@ -0,0 +1,8 @@
+import _iga1 from "./packages/legacy/lib/main.js";
+import _iga2 from "./packages/markdown/dist/index.mjs";
+import _iga3 from "./packages/toc/index.js";
+const plugins = {
+    legacy: _iga1,
+    markdown: _iga2,
+    toc: _iga3
+};
@@ -0,0 +1,3 @@
+import locales from "locales-pkg/dist/locales/*.js";
+import internals from "locales-pkg/dist/internal/*.js";
+import icons from "plain-pkg/icons/*.svg";
@@ -0,0 +1,11 @@
+import _iga1 from "locales-pkg/locales/en";
+import _iga2 from "locales-pkg/locales/fr";
+const locales = [
+    _iga1,
+    _iga2
+];
+const internals = [];
+import _iga3 from "plain-pkg/icons/close.svg";
+const icons = [
+    _iga3
+];
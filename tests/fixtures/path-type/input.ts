@@ -0,0 +1,5 @@
+// import-glob: pathType=PagePath
+import pages from "./pages/*.ts";
+export function getPage(path: PagePath) {
+    return pages.find((page)=>page.path === path);
+}
@@ -0,0 +1,10 @@
+import _iga1 from "./pages/about.ts";
+import _iga2 from "./pages/home.ts";
+const pages = [
+    _iga1,
+    _iga2
+];
+type PagePath = "./pages/about.ts" | "./pages/home.ts";
+export function getPage(path: PagePath) {
+    return pages.find((page)=>page.path === path);
+}
@@ -0,0 +1 @@
+import icons from "@/icons/*.svg";
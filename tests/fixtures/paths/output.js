@@ -0,0 +1,6 @@
+import _iga1 from "./generated/icons/logo.svg";
+import _iga2 from "./generated/icons/menu.svg";
+const icons = [
+    _iga1,
+    _iga2
+];
@@ -0,0 +1 @@
+import handlers from "#app/handlers/*.js";
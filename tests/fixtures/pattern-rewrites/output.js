@@ -0,0 +1,4 @@
+import _iga1 from "./src/app/handlers/click.js";
+const handlers = [
+    _iga1
+];
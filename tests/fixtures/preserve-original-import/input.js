@@ -0,0 +1 @@
+import docs, { title as titles } from "./docs/*.md";
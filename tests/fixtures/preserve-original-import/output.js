@@ -0,0 +1,8 @@
+// import docs, { title as titles } from "./docs/*.md";
+import _iga1, { title as _iga2 } from "./docs/hello.md";
+const docs = [
+    _iga1
+];
+const titles = [
+    _iga2
+];
@@ -0,0 +1,7 @@
+const plugins = [
+    import.meta.resolve("./plugins/markdown.js"),
+    import.meta.resolve("./plugins/toc.js")
+];
+const workers = plugins.map((plugin)=>new Worker(plugin, {
+        type: "module"
+    }));
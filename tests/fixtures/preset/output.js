@@ -0,0 +1,6 @@
+import _iga1 from "./docs/page-2.md";
+import _iga2 from "./docs/page-10.md";
+const docs = [
+    _iga1,
+    _iga2
+];
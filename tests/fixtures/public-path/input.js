@@ -0,0 +1 @@
+import images from "./images/*.png";
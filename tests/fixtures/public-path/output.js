@@ -0,0 +1,3 @@
+const images = [
+    "/static/images/dot.png"
+];
@@ -0,0 +1,2 @@
+import queries from "./queries/*.sql";
+import { title } from "./locales/*.json";
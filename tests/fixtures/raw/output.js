@@ -0,0 +1,8 @@
+import _iga1 from "./queries/zz-large.sql";
+const queries = [
+    "SELECT * FROM users;\n",
+    _iga1
+];
+const title = [
+    "Hello"
+];
@@ -0,0 +1,12 @@
+import _iga1 from "./posts/hello.md";
+const posts = [
+    _iga1
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/reading-time/posts/hello.md",
+        importedPath: "./posts/hello.md",
+        wordCount: 12,
+        readingTime: 3
+    }
+];
@@ -0,0 +1,7 @@
+import "./packages/linked/index.js";
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/real-paths/vendor/linked/index.js",
+        importedPath: "./packages/linked/index.js"
+    }
+];
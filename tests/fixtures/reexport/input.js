@@ -0,0 +1,2 @@
+export * from "./utils/*.js";
+export * as docs from "./docs/*.md";
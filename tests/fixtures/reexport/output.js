@@ -0,0 +1,7 @@
+export * from "./utils/format.js";
+export * from "./utils/parse.js";
+import * as _iga1 from "./docs/hello.md";
+const docs = [
+    _iga1
+];
+export { docs };
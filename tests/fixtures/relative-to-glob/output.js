@@ -0,0 +1,14 @@
+import "./content/guides/intro.md";
+import "./content/index.md";
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/relative-to-glob/content/guides/intro.md",
+        importedPath: "./content/guides/intro.md",
+        relativeToGlob: "guides/intro.md"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/relative-to-glob/content/index.md",
+        importedPath: "./content/index.md",
+        relativeToGlob: "index.md"
+    }
+];
@@ -0,0 +1,2 @@
+const migrations = require.resolve("./migrations/*.sql");
+const config = require.resolve("./config.json");
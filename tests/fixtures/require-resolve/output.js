@@ -0,0 +1,5 @@
+const migrations = [
+    "$DIR/tests/fixtures/require-resolve/migrations/001-users.sql",
+    "$DIR/tests/fixtures/require-resolve/migrations/002-posts.sql"
+];
+const config = require.resolve("./config.json");
@@ -0,0 +1 @@
+import { title as titles } from "./docs/*.md";
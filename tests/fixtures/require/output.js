@@ -0,0 +1,6 @@
+const _iga1 = require("./docs/intro.md");
+const _iga2 = require("./docs/setup.md");
+const titles = [
+    _iga1.title,
+    _iga2.title
+];
@@ -0,0 +1 @@
+import pages, { _importMeta as meta } from "./src/pages/*.js";
@@ -0,0 +1,10 @@
+import _iga1 from "src/pages/about.js";
+const pages = [
+    _iga1
+];
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/root-relative/src/pages/about.js",
+        importedPath: "src/pages/about.js"
+    }
+];
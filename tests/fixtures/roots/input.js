@@ -0,0 +1 @@
+import handlers from "./handlers/*.js";
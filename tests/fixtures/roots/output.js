@@ -0,0 +1,6 @@
+import _iga1 from "./handlers/click.js";
+import _iga2 from "./legacy/handlers/submit.js";
+const handlers = [
+    _iga1,
+    _iga2
+];
@@ -0,0 +1,6 @@
+import { pick as _igaPick } from "swc-import-glob-array-plugin/runtime.mjs";
+import { wrapImportError as _igaWrapImportError } from "swc-import-glob-array-plugin/runtime.mjs";
+const pages = [
+    () => import("./pages/about.js").then(_igaPick("default")).catch(_igaWrapImportError("./pages/about.js")),
+    () => import("./pages/home.js").then(_igaPick("default")).catch(_igaWrapImportError("./pages/home.js"))
+];
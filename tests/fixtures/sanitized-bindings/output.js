@@ -0,0 +1,11 @@
+import _iga1 from "./pages/404.js";
+import _iga2 from "./pages/class.js";
+import _iga3 from "./pages/user-profile.js";
+const pages = [
+    _iga1,
+    _iga2,
+    _iga3
+];
+export const _404 = _iga1;
+export const Class = _iga2;
+export const UserProfile = _iga3;
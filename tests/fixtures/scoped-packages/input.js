@@ -0,0 +1,2 @@
+import plugins from "@acme/*/plugin-entry.js";
+import * as packages from "@acme/*";
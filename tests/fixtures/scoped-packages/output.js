@@ -0,0 +1,14 @@
+import _iga1 from "@acme/plugin-a/plugin-entry.js";
+import _iga2 from "@acme/plugin-b/plugin-entry.js";
+const plugins = [
+    _iga1,
+    _iga2
+];
+import * as _iga3 from "@acme/plugin-a";
+import * as _iga4 from "@acme/plugin-b";
+import * as _iga5 from "@acme/utils";
+const packages = [
+    _iga3,
+    _iga4,
+    _iga5
+];
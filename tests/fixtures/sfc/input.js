@@ -0,0 +1,2 @@
+import * as components from "./components/*.vue";
+import { _importMeta as widgetMeta } from "./widgets/*.svelte";
@@ -0,0 +1,13 @@
+import * as _iga1 from "./components/Button.vue";
+import * as _iga2 from "./components/Modal.vue";
+const components = [
+    _iga1,
+    _iga2
+];
+import "./widgets/Card.svelte";
+const widgetMeta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/sfc/widgets/Card.svelte",
+        importedPath: "./widgets/Card.svelte"
+    }
+];
@@ -0,0 +1,10 @@
+import _iga1 from "./docs/introduction.md";
+import _iga2 from "./docs/installation.md";
+import _iga3 from "./docs/advanced.md";
+import _iga4 from "./docs/changelog.md";
+const docs = [
+    _iga1,
+    _iga2,
+    _iga3,
+    _iga4
+];
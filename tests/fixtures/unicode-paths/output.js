@@ -0,0 +1,12 @@
+import "./menu/bistro.md";
+import "./menu/café.md";
+const meta = [
+    {
+        absolutePath: "$DIR/tests/fixtures/unicode-paths/menu/bistro.md",
+        importedPath: "./menu/bistro.md"
+    },
+    {
+        absolutePath: "$DIR/tests/fixtures/unicode-paths/menu/café.md",
+        importedPath: "./menu/café.md"
+    }
+];
@@ -0,0 +1 @@
+import pages from "./pages/*.js";
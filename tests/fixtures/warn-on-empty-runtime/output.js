@@ -0,0 +1,7 @@
+import _iga1 from "./pages/home.js";
+const pages = [
+    _iga1
+];
+if (process.env.NODE_ENV !== "production" && pages.length === 0) {
+    console.warn("import-glob-array: \"./pages/*.js\" didn't match any files");
+}
@@ -0,0 +1 @@
+import drafts from "./drafts/*.md";
@@ -0,0 +1,4 @@
+const drafts = [];
+if (process.env.NODE_ENV !== "production" && drafts.length === 0) {
+    console.warn("import-glob-array: \"./drafts/*.md\" didn't match any files");
+}
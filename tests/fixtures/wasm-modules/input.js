@@ -0,0 +1,3 @@
+import modules from "./wasm/*.wasm";
+// import-glob: lazy
+import loaders from "./wasm/*.wasm";
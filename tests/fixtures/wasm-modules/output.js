@@ -0,0 +1,10 @@
+import * as _iga1 from "./wasm/add.wasm";
+import * as _iga2 from "./wasm/hash.wasm";
+const modules = [
+    _iga1,
+    _iga2
+];
+const loaders = [
+    () => import("./wasm/add.wasm"),
+    () => import("./wasm/hash.wasm")
+];
@@ -0,0 +1 @@
+import modules from "./wasm/*.wasm";
@@ -0,0 +1,4 @@
+const modules = [
+    new URL("./wasm/add.wasm", import.meta.url).href,
+    new URL("./wasm/hash.wasm", import.meta.url).href
+];
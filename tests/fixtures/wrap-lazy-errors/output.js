@@ -0,0 +1,9 @@
+const pages = [
+    () => import("./pages/about.js").then((m) => m.default).catch((cause) => {
+        throw Object.assign(new Error("Failed to import ./pages/about.js", {
+            cause: cause
+        }), {
+            importedPath: "./pages/about.js"
+        });
+    })
+];